@@ -1,11 +1,16 @@
+@group(1) @binding(0) var font_texture: texture_2d<f32>;
+@group(1) @binding(1) var font_sampler: sampler;
+
 struct VertexInput {
     @location(0) position: vec2<f32>,
     @location(1) color: vec4<f32>,
+    @location(2) uv: vec2<f32>,
 };
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
 };
 
 
@@ -14,11 +19,12 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     var position = vec4(vertex.position.x, -vertex.position.y, 0.5, 1.0);
     var color = vertex.color;
 
-    return VertexOutput(position, color);
+    return VertexOutput(position, color, vertex.uv);
 }
 
 struct FragmentInput {
     @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
 };
 
 struct FragmentOutput {
@@ -27,5 +33,5 @@ struct FragmentOutput {
 
 @fragment
 fn fragment(in: FragmentInput) -> FragmentOutput {
-    return FragmentOutput(in.color);
+    return FragmentOutput(in.color * textureSample(font_texture, font_sampler, in.uv));
 }
@@ -31,18 +31,22 @@
 use bevy_app::prelude::*;
 use bevy_asset::{AssetApp, Assets};
 use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
+use bevy_input::keyboard::{Key, KeyboardInput};
 use bevy_input::prelude::*;
-use bevy_math::{DQuat, DVec3, Vec2};
+use bevy_input::ButtonState;
+use bevy_math::{DMat4, DQuat, DVec3, Quat, Vec2, Vec3};
 use bevy_render::prelude::*;
 use bevy_transform::prelude::*;
 use bevy_utils::HashMap;
 use bevy_window::{PrimaryWindow, Window};
+use epaint::{Rgba, WHITE_UV};
 use uuid::Uuid;
 
-use render::{DrawDataHandles, TransformGizmoRenderPlugin};
+use render::{DrawDataHandles, GizmoFontAtlas, TransformGizmoRenderPlugin};
 use transform_gizmo::config::{
-    GizmoModeKind, TransformPivotPoint, DEFAULT_SNAP_ANGLE, DEFAULT_SNAP_DISTANCE,
-    DEFAULT_SNAP_SCALE,
+    ClipSpace, DepthRange, GizmoModeKind, SnapMode, TransformPivotPoint, DEFAULT_SNAP_ANGLE,
+    DEFAULT_SNAP_DISTANCE, DEFAULT_SNAP_SCALE,
 };
 pub use transform_gizmo::{
     math::{Pos2, Rect},
@@ -81,8 +85,18 @@ pub struct GizmoOptions {
     pub gizmo_modes: EnumSet<GizmoMode>,
     /// Orientation of the gizmo. This affects the behaviour of transformations.
     pub gizmo_orientation: GizmoOrientation,
+    /// Rotation of the gizmo axes with [`GizmoOrientation::Custom`].
+    pub custom_orientation: Quat,
     /// Orientation of the gizmo. This affects the behaviour of transformations.
     pub pivot_point: TransformPivotPoint,
+    /// Position of the pivot with [`TransformPivotPoint::Point`], in world space.
+    pub pivot_position: Vec3,
+    /// Gizmo axes along which translation is locked.
+    pub locked_translation: EnumSet<GizmoDirection>,
+    /// Gizmo axes around which rotation is locked.
+    pub locked_rotation: EnumSet<GizmoDirection>,
+    /// Gizmo axes along which scale is locked.
+    pub locked_scale: EnumSet<GizmoDirection>,
     /// Look and feel of the gizmo.
     pub visuals: GizmoVisuals,
     /// Whether snapping is enabled in the gizmo transformations.
@@ -91,12 +105,23 @@ pub struct GizmoOptions {
     /// When snapping is enabled, snap twice as often.
     /// This may be overwritten with hotkeys ([`GizmoHotkeys::enable_accurate_mode`]).
     pub accurate_mode: bool,
+    /// Whether snapping is relative to the start of the interaction or to the world grid.
+    pub snap_mode: SnapMode,
     /// Angle increment for snapping rotations, in radians.
     pub snap_angle: f32,
     /// Distance increment for snapping translations.
     pub snap_distance: f32,
     /// Scale increment for snapping scalings.
     pub snap_scale: f32,
+    /// Angle increments for snapping rotations around each gizmo axis, in radians.
+    /// Overrides `snap_angle` for rotations around the X, Y and Z axes.
+    pub snap_angle_per_axis: Option<Vec3>,
+    /// Distance increments for snapping translations along each gizmo axis.
+    /// Overrides `snap_distance`.
+    pub snap_distance_per_axis: Option<Vec3>,
+    /// Scale increments for snapping scalings along each gizmo axis.
+    /// Overrides `snap_scale`.
+    pub snap_scale_per_axis: Option<Vec3>,
     /// If `true`, all [`GizmoTarget`]s are transformed
     /// using a single gizmo. If `false`, each target
     /// has its own gizmo.
@@ -115,15 +140,24 @@ pub struct GizmoOptions {
 impl Default for GizmoOptions {
     fn default() -> Self {
         Self {
-            gizmo_modes: GizmoMode::all(),
+            gizmo_modes: GizmoMode::all() - GizmoMode::all_bounds(),
             gizmo_orientation: GizmoOrientation::default(),
+            custom_orientation: Quat::IDENTITY,
             pivot_point: TransformPivotPoint::default(),
+            pivot_position: Vec3::ZERO,
+            locked_translation: EnumSet::empty(),
+            locked_rotation: EnumSet::empty(),
+            locked_scale: EnumSet::empty(),
             visuals: Default::default(),
             snapping: false,
             accurate_mode: false,
+            snap_mode: SnapMode::default(),
             snap_angle: DEFAULT_SNAP_ANGLE,
             snap_distance: DEFAULT_SNAP_DISTANCE,
             snap_scale: DEFAULT_SNAP_SCALE,
+            snap_angle_per_axis: None,
+            snap_distance_per_axis: None,
+            snap_scale_per_axis: None,
             group_targets: true,
             mode_override: None,
             hotkeys: None,
@@ -197,6 +231,9 @@ pub struct GizmoTarget {
     /// This gets replaced with the result of the most recent
     /// gizmo interaction that affected this entity.
     pub(crate) latest_result: Option<GizmoResult>,
+
+    /// Phase of the drag interaction during the latest update.
+    pub(crate) drag_phase: Option<GizmoDragPhase>,
 }
 
 impl GizmoTarget {
@@ -215,6 +252,14 @@ impl GizmoTarget {
     pub fn latest_result(&self) -> Option<GizmoResult> {
         self.latest_result
     }
+
+    /// Phase of the drag interaction during the latest update, see [`Gizmo::drag_phase`].
+    ///
+    /// Pressing Escape cancels the active interaction and restores the transform
+    /// the entity had at its start. The phase is then [`GizmoDragPhase::Cancelled`].
+    pub fn drag_phase(&self) -> Option<GizmoDragPhase> {
+        self.drag_phase
+    }
 }
 
 /// Marker used to specify which camera to use for gizmos.
@@ -345,6 +390,7 @@ fn handle_hotkeys(
                     GizmoModeKind::Translate => GizmoMode::TranslateView,
                     GizmoModeKind::Scale => GizmoMode::ScaleUniform,
                     GizmoModeKind::Arcball => GizmoMode::Arcball,
+                    GizmoModeKind::Bounds => GizmoMode::BoundsFace,
                 })
             })
     });
@@ -364,8 +410,13 @@ fn handle_hotkeys(
 fn update_gizmos(
     q_window: Query<&Window, With<PrimaryWindow>>,
     q_gizmo_camera: Query<(&Camera, &GlobalTransform), With<GizmoCamera>>,
-    mut q_targets: Query<(Entity, &mut Transform, &mut GizmoTarget), Without<GizmoCamera>>,
+    mut q_targets: Query<
+        (Entity, &mut Transform, &mut GizmoTarget, Option<&Parent>),
+        Without<GizmoCamera>,
+    >,
+    q_parents: Query<&GlobalTransform>,
     mouse: Res<ButtonInput<MouseButton>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
     gizmo_options: Res<GizmoOptions>,
     mut gizmo_storage: ResMut<GizmoStorage>,
     mut last_cursor_pos: Local<Vec2>,
@@ -424,32 +475,72 @@ fn update_gizmos(
 
     let projection_matrix = camera.clip_from_view();
 
-    let view_matrix = camera_transform.compute_matrix().inverse();
+    // The view matrix is inverted in f64, so that the translation of far away
+    // cameras keeps its precision when rebased to the origin offset.
+    let (camera_scale, camera_rotation, camera_translation) =
+        camera_transform.to_scale_rotation_translation();
+    let view_matrix = DMat4::from_scale_rotation_translation(
+        camera_scale.as_dvec3(),
+        camera_rotation.as_dquat(),
+        camera_translation.as_dvec3(),
+    )
+    .inverse();
 
     let mut snap_angle = gizmo_options.snap_angle;
     let mut snap_distance = gizmo_options.snap_distance;
     let mut snap_scale = gizmo_options.snap_scale;
+    let mut snap_angle_per_axis = gizmo_options.snap_angle_per_axis;
+    let mut snap_distance_per_axis = gizmo_options.snap_distance_per_axis;
+    let mut snap_scale_per_axis = gizmo_options.snap_scale_per_axis;
 
     if gizmo_options.accurate_mode {
         snap_angle /= 2.0;
         snap_distance /= 2.0;
         snap_scale /= 2.0;
+
+        for snap in [
+            &mut snap_angle_per_axis,
+            &mut snap_distance_per_axis,
+            &mut snap_scale_per_axis,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *snap /= 2.0;
+        }
     }
 
     let gizmo_config = GizmoConfig {
-        view_matrix: view_matrix.as_dmat4().into(),
+        view_matrix: view_matrix.into(),
         projection_matrix: projection_matrix.as_dmat4().into(),
+        // Bevy uses reversed-Z projections with depth from 0 to 1
+        clip_space: ClipSpace {
+            depth_range: DepthRange::ZeroToOne,
+            reversed_z: true,
+            y_down: false,
+        },
         viewport,
+        origin_offset: camera_translation.as_dvec3().into(),
         modes: gizmo_options.gizmo_modes,
         mode_override: gizmo_options.mode_override,
         orientation: gizmo_options.gizmo_orientation,
+        custom_orientation: gizmo_options.custom_orientation.as_dquat().into(),
         pivot_point: gizmo_options.pivot_point,
+        pivot_position: gizmo_options.pivot_position.as_dvec3().into(),
+        locked_translation: gizmo_options.locked_translation,
+        locked_rotation: gizmo_options.locked_rotation,
+        locked_scale: gizmo_options.locked_scale,
         visuals: gizmo_options.visuals,
         snapping: gizmo_options.snapping,
+        snap_mode: gizmo_options.snap_mode,
         snap_angle,
         snap_distance,
         snap_scale,
+        snap_angle_per_axis: snap_angle_per_axis.map(Into::into),
+        snap_distance_per_axis: snap_distance_per_axis.map(Into::into),
+        snap_scale_per_axis: snap_scale_per_axis.map(Into::into),
         pixels_per_point: scale_factor,
+        ..Default::default()
     };
 
     let gizmo_interaction = GizmoInteraction {
@@ -459,14 +550,43 @@ fn update_gizmos(
         dragging: mouse.any_pressed([MouseButton::Left]),
     };
 
+    let key_events = keyboard_events
+        .read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .collect::<Vec<_>>();
+
+    // Escape cancels the active interaction
+    let cancel = key_events
+        .iter()
+        .any(|event| event.logical_key == Key::Escape);
+
+    // Keys for typing exact values. These are ignored by gizmos that are not active.
+    let typed_keys = key_events
+        .iter()
+        .flat_map(|event| match &event.logical_key {
+            Key::Character(text) => text.chars().map(GizmoKey::Char).collect(),
+            Key::Backspace => vec![GizmoKey::Backspace],
+            Key::Enter => vec![GizmoKey::Enter],
+            _ => vec![],
+        })
+        .collect::<Vec<_>>();
+
     let mut target_entities: Vec<Entity> = vec![];
     let mut target_transforms: Vec<Transform> = vec![];
+    let mut target_parents: Vec<mint::ColumnMatrix4<f64>> = vec![];
+
+    for (entity, mut target_transform, mut gizmo_target, parent) in &mut q_targets {
+        // Targets are edited in the local space of their parents
+        let parent_matrix = parent
+            .and_then(|parent| q_parents.get(parent.get()).ok())
+            .map_or(DMat4::IDENTITY, |parent| parent.compute_matrix().as_dmat4())
+            .into();
 
-    for (entity, mut target_transform, mut gizmo_target) in &mut q_targets {
         target_entities.push(entity);
-        target_transforms.push(*target_transform);
+        target_parents.push(parent_matrix);
 
         if gizmo_options.group_targets {
+            target_transforms.push(*target_transform);
             gizmo_storage
                 .entity_gizmo_map
                 .insert(entity, GIZMO_GROUP_UUID);
@@ -485,21 +605,44 @@ fn update_gizmos(
         }
 
         let gizmo = gizmo_storage.gizmos.entry(gizmo_uuid).or_default();
+
+        // Cancel before updating the configuration, which would commit the interaction
+        // if the deactivation hotkey cleared the mode override.
+        let mut cancelled = false;
+        if cancel {
+            if let Some(start_targets) = gizmo.cancel() {
+                if let Some(start_target) = start_targets.first() {
+                    set_transform(&mut target_transform, start_target);
+                }
+                cancelled = true;
+            }
+        }
+
         gizmo.update_config(gizmo_config);
 
-        let gizmo_result = gizmo.update(
+        for key in &typed_keys {
+            gizmo.type_key(*key);
+        }
+
+        let gizmo_result = gizmo.update_with_parents(
             gizmo_interaction,
             &[math::Transform {
                 translation: target_transform.translation.as_dvec3().into(),
                 rotation: target_transform.rotation.as_dquat().into(),
                 scale: target_transform.scale.as_dvec3().into(),
             }],
+            &[parent_matrix],
         );
 
         let is_focused = gizmo.is_focused();
 
         gizmo_target.is_active = gizmo_result.is_some();
         gizmo_target.is_focused = is_focused;
+        gizmo_target.drag_phase = if cancelled {
+            Some(GizmoDragPhase::Cancelled)
+        } else {
+            gizmo.drag_phase()
+        };
 
         if let Some((_, updated_targets)) = &gizmo_result {
             let Some(result_transform) = updated_targets.first() else {
@@ -507,9 +650,7 @@ fn update_gizmos(
                 continue;
             };
 
-            target_transform.translation = DVec3::from(result_transform.translation).as_vec3();
-            target_transform.rotation = DQuat::from(result_transform.rotation).as_quat();
-            target_transform.scale = DVec3::from(result_transform.scale).as_vec3();
+            set_transform(&mut target_transform, result_transform);
         }
 
         gizmo_target.latest_result = gizmo_result.map(|(result, _)| result);
@@ -517,9 +658,29 @@ fn update_gizmos(
 
     if gizmo_options.group_targets {
         let gizmo = gizmo_storage.gizmos.entry(GIZMO_GROUP_UUID).or_default();
+
+        // Cancel before updating the configuration, as above
+        let mut cancelled = false;
+        if cancel {
+            if let Some(start_targets) = gizmo.cancel() {
+                for ((_, mut target_transform, _, _), (transform, start_target)) in q_targets
+                    .iter_mut()
+                    .zip(target_transforms.iter_mut().zip(&start_targets))
+                {
+                    set_transform(&mut target_transform, start_target);
+                    *transform = *target_transform;
+                }
+                cancelled = true;
+            }
+        }
+
         gizmo.update_config(gizmo_config);
 
-        let gizmo_result = gizmo.update(
+        for key in &typed_keys {
+            gizmo.type_key(*key);
+        }
+
+        let gizmo_result = gizmo.update_with_parents(
             gizmo_interaction,
             target_transforms
                 .iter()
@@ -530,13 +691,20 @@ fn update_gizmos(
                 })
                 .collect::<Vec<_>>()
                 .as_slice(),
+            &target_parents,
         );
 
         let is_focused = gizmo.is_focused();
 
-        for (i, (_, mut target_transform, mut gizmo_target)) in q_targets.iter_mut().enumerate() {
+        for (i, (_, mut target_transform, mut gizmo_target, _)) in q_targets.iter_mut().enumerate()
+        {
             gizmo_target.is_active = gizmo_result.is_some();
             gizmo_target.is_focused = is_focused;
+            gizmo_target.drag_phase = if cancelled {
+                Some(GizmoDragPhase::Cancelled)
+            } else {
+                gizmo.drag_phase()
+            };
 
             if let Some((_, updated_targets)) = &gizmo_result {
                 let Some(result_transform) = updated_targets.get(i) else {
@@ -544,9 +712,7 @@ fn update_gizmos(
                     continue;
                 };
 
-                target_transform.translation = DVec3::from(result_transform.translation).as_vec3();
-                target_transform.rotation = DQuat::from(result_transform.rotation).as_quat();
-                target_transform.scale = DVec3::from(result_transform.scale).as_vec3();
+                set_transform(&mut target_transform, result_transform);
             }
 
             gizmo_target.latest_result = gizmo_result.as_ref().map(|(result, _)| *result);
@@ -556,13 +722,32 @@ fn update_gizmos(
     gizmo_storage.target_entities = target_entities;
 }
 
+/// Sets the translation, rotation and scale of a target from a gizmo transform.
+fn set_transform(target: &mut Transform, transform: &math::Transform) {
+    target.translation = DVec3::from(transform.translation).as_vec3();
+    target.rotation = DQuat::from(transform.rotation).as_quat();
+    target.scale = DVec3::from(transform.scale).as_vec3();
+}
+
 fn draw_gizmos(
     gizmo_storage: Res<GizmoStorage>,
+    font_atlas: Res<GizmoFontAtlas>,
+    mut images: ResMut<Assets<Image>>,
     mut draw_data_assets: ResMut<Assets<render::GizmoDrawData>>,
     mut draw_data_handles: ResMut<DrawDataHandles>,
 ) {
+    let pixels_per_point = gizmo_storage
+        .gizmos
+        .values()
+        .next()
+        .map_or(1.0, |gizmo| gizmo.config().pixels_per_point);
+    font_atlas
+        .fonts
+        .begin_pass(pixels_per_point, render::FONT_ATLAS_MAX_SIDE);
+
     for (gizmo_uuid, gizmo) in &gizmo_storage.gizmos {
         let draw_data = gizmo.draw();
+        let text_mesh = font_atlas.tessellate(&draw_data.texts);
 
         let mut bevy_draw_data = render::GizmoDrawData::default();
 
@@ -575,19 +760,51 @@ fn draw_gizmos(
 
         let viewport = &gizmo.config().viewport;
 
-        asset.0.vertices.clear();
-        asset
-            .0
-            .vertices
-            .extend(draw_data.vertices.into_iter().map(|vert| {
-                [
-                    ((vert[0] - viewport.left()) / viewport.width()) * 2.0 - 1.0,
-                    ((vert[1] - viewport.top()) / viewport.height()) * 2.0 - 1.0,
-                ]
-            }));
+        let vertex_count = draw_data.vertices.len();
+
+        asset.vertices.clear();
+        asset.vertices.extend(
+            draw_data
+                .vertices
+                .into_iter()
+                .chain(
+                    text_mesh
+                        .vertices
+                        .iter()
+                        .map(|vertex| [vertex.pos.x, vertex.pos.y]),
+                )
+                .map(|vert| {
+                    [
+                        ((vert[0] - viewport.left()) / viewport.width()) * 2.0 - 1.0,
+                        ((vert[1] - viewport.top()) / viewport.height()) * 2.0 - 1.0,
+                    ]
+                }),
+        );
 
-        asset.0.colors = draw_data.colors;
-        asset.0.indices = draw_data.indices;
+        asset.uvs.clear();
+        asset.uvs.resize(vertex_count, [WHITE_UV.x, WHITE_UV.y]);
+        asset.uvs.extend(
+            text_mesh
+                .vertices
+                .iter()
+                .map(|vertex| [vertex.uv.x, vertex.uv.y]),
+        );
+
+        asset.colors = draw_data.colors;
+        asset.colors.extend(
+            text_mesh
+                .vertices
+                .iter()
+                .map(|vertex| Rgba::from(vertex.color).to_array()),
+        );
+
+        asset.indices = draw_data.indices;
+        asset.indices.extend(
+            text_mesh
+                .indices
+                .into_iter()
+                .map(|index| index + vertex_count as u32),
+        );
 
         if is_new_asset {
             let asset = draw_data_assets.add(bevy_draw_data);
@@ -595,6 +812,8 @@ fn draw_gizmos(
             draw_data_handles.handles.insert(*gizmo_uuid, asset.clone());
         }
     }
+
+    font_atlas.update_image(&mut images);
 }
 
 fn cleanup_old_data(
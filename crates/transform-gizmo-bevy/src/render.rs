@@ -1,5 +1,5 @@
 use bevy_app::{App, Plugin};
-use bevy_asset::{load_internal_asset, Asset, Handle};
+use bevy_asset::{load_internal_asset, Asset, Assets, Handle};
 use bevy_core_pipeline::core_3d::{Transparent3d, CORE_3D_DEPTH_FORMAT};
 use bevy_core_pipeline::prepass::{
     DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass,
@@ -20,23 +20,33 @@ use bevy_render::render_phase::{
     AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand,
     RenderCommandResult, SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
 };
+use bevy_render::render_resource::binding_types::{sampler, texture_2d};
 use bevy_render::render_resource::{
-    BlendState, Buffer, BufferInitDescriptor, BufferUsages, ColorTargetState, ColorWrites,
-    CompareFunction, DepthBiasState, DepthStencilState, FragmentState, IndexFormat,
-    MultisampleState, PipelineCache, PrimitiveState, RenderPipelineDescriptor,
-    SpecializedRenderPipeline, SpecializedRenderPipelines, StencilState, TextureFormat,
-    VertexAttribute, VertexBufferLayout, VertexFormat, VertexState, VertexStepMode,
+    BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BlendState, Buffer,
+    BufferInitDescriptor, BufferUsages, ColorTargetState, ColorWrites, CompareFunction,
+    DepthBiasState, DepthStencilState, Extent3d, FragmentState, IndexFormat, MultisampleState,
+    PipelineCache, PrimitiveState, RenderPipelineDescriptor, SamplerBindingType, ShaderStages,
+    SpecializedRenderPipeline, SpecializedRenderPipelines, StencilState, TextureDimension,
+    TextureFormat, TextureSampleType, VertexAttribute, VertexBufferLayout, VertexFormat,
+    VertexState, VertexStepMode,
 };
 use bevy_render::renderer::RenderDevice;
-use bevy_render::texture::BevyDefault;
+use bevy_render::texture::{BevyDefault, GpuImage, ImageSampler};
 use bevy_render::view::{ExtractedView, RenderLayers, ViewTarget};
 use bevy_render::{Extract, Render, RenderApp, RenderSet};
 use bevy_utils::{HashMap, HashSet};
 use bytemuck::cast_slice;
+use epaint::emath::Align2;
+use epaint::text::{FontDefinitions, FontId, Fonts};
+use epaint::{Color32, Mesh, Pos2, Rgba, Shape, Tessellator};
+use transform_gizmo::GizmoText;
 use uuid::Uuid;
 
 const GIZMO_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(7414812681337026784);
 
+/// Maximum side length of the glyph atlas texture.
+pub(crate) const FONT_ATLAS_MAX_SIDE: usize = 2048;
+
 pub(crate) struct TransformGizmoRenderPlugin;
 
 impl Plugin for TransformGizmoRenderPlugin {
@@ -55,13 +65,18 @@ impl Plugin for TransformGizmoRenderPlugin {
             .init_resource::<SpecializedRenderPipelines<TransformGizmoPipeline>>()
             .add_systems(
                 Render,
-                queue_transform_gizmos
-                    .in_set(RenderSet::Queue)
-                    .after(prepare_assets::<GizmoBuffers>),
+                (
+                    prepare_font_bind_group.in_set(RenderSet::PrepareBindGroups),
+                    queue_transform_gizmos
+                        .in_set(RenderSet::Queue)
+                        .after(prepare_assets::<GizmoBuffers>),
+                ),
             );
     }
 
     fn finish(&self, app: &mut App) {
+        app.init_resource::<GizmoFontAtlas>();
+
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
         };
@@ -77,7 +92,95 @@ pub(crate) struct DrawDataHandles {
     pub(crate) handles: HashMap<Uuid, Handle<GizmoDrawData>>,
 }
 
-fn extract_gizmo_data(mut commands: Commands, handles: Extract<Res<DrawDataHandles>>) {
+/// Fonts used for the gizmo texts and the glyph atlas image they are rendered from.
+#[derive(Resource)]
+pub(crate) struct GizmoFontAtlas {
+    pub(crate) fonts: Fonts,
+    image: Handle<Image>,
+}
+
+impl FromWorld for GizmoFontAtlas {
+    fn from_world(world: &mut World) -> Self {
+        let fonts = Fonts::new(1.0, FONT_ATLAS_MAX_SIDE, FontDefinitions::default());
+        let image = world
+            .resource_mut::<Assets<Image>>()
+            .add(font_atlas_image(&fonts));
+
+        Self { fonts, image }
+    }
+}
+
+impl GizmoFontAtlas {
+    /// Uploads the glyph atlas again if new glyphs were rasterized since the last call.
+    pub(crate) fn update_image(&self, images: &mut Assets<Image>) {
+        if self.fonts.font_image_delta().is_some() {
+            images.insert(&self.image, font_atlas_image(&self.fonts));
+        }
+    }
+
+    /// Lays out the texts centered on their positions and tessellates them
+    /// into triangles textured with the glyph atlas.
+    pub(crate) fn tessellate(&self, texts: &[GizmoText]) -> Mesh {
+        let mut tessellator = Tessellator::new(
+            self.fonts.pixels_per_point(),
+            Default::default(),
+            self.fonts.font_image_size(),
+            Vec::new(),
+        );
+
+        let mut mesh = Mesh::default();
+        for text in texts {
+            let [r, g, b, a] = text.color;
+            let color = Color32::from(Rgba::from_rgba_premultiplied(r, g, b, a));
+            let galley = self.fonts.layout_no_wrap(
+                text.text.clone(),
+                FontId::proportional(text.size),
+                color,
+            );
+            let rect = Align2::CENTER_CENTER.anchor_size(Pos2::from(text.position), galley.size());
+
+            tessellator.tessellate_shape(Shape::galley(rect.min, galley, color), &mut mesh);
+        }
+
+        mesh
+    }
+}
+
+fn font_atlas_image(fonts: &Fonts) -> Image {
+    let font_image = fonts.image();
+
+    let mut image = Image::new(
+        Extent3d {
+            width: font_image.width() as u32,
+            height: font_image.height() as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        font_image
+            .srgba_pixels(None)
+            .flat_map(|color| color.to_array())
+            .collect(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::linear();
+
+    image
+}
+
+#[derive(Resource)]
+struct ExtractedFontAtlas(Handle<Image>);
+
+#[derive(Resource)]
+struct GizmoFontBindGroup(BindGroup);
+
+fn extract_gizmo_data(
+    mut commands: Commands,
+    handles: Extract<Res<DrawDataHandles>>,
+    font_atlas: Extract<Res<GizmoFontAtlas>>,
+) {
+    commands.insert_resource(ExtractedFontAtlas(font_atlas.image.clone_weak()));
+
     let handle_weak_refs = handles
         .handles
         .values()
@@ -89,14 +192,42 @@ fn extract_gizmo_data(mut commands: Commands, handles: Extract<Res<DrawDataHandl
     }
 }
 
+fn prepare_font_bind_group(
+    mut commands: Commands,
+    pipeline: Res<TransformGizmoPipeline>,
+    font_atlas: Option<Res<ExtractedFontAtlas>>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_device: Res<RenderDevice>,
+) {
+    let Some(image) = font_atlas.and_then(|font_atlas| images.get(&font_atlas.0)) else {
+        return;
+    };
+
+    let bind_group = render_device.create_bind_group(
+        "TransformGizmo Font Bind Group",
+        &pipeline.font_layout,
+        &BindGroupEntries::sequential((&image.texture_view, &image.sampler)),
+    );
+
+    commands.insert_resource(GizmoFontBindGroup(bind_group));
+}
+
+/// Gizmo shapes and texts in normalized device coordinates.
+/// Shapes sample the white texel of the glyph atlas.
 #[derive(Asset, Debug, Default, Clone, TypePath)]
-pub(crate) struct GizmoDrawData(pub(crate) transform_gizmo::GizmoDrawData);
+pub(crate) struct GizmoDrawData {
+    pub(crate) vertices: Vec<[f32; 2]>,
+    pub(crate) uvs: Vec<[f32; 2]>,
+    pub(crate) colors: Vec<[f32; 4]>,
+    pub(crate) indices: Vec<u32>,
+}
 
 #[derive(Debug, Clone)]
 pub(crate) struct GizmoBuffers {
     position_buffer: Buffer,
     index_buffer: Buffer,
     color_buffer: Buffer,
+    uv_buffer: Buffer,
     index_count: u32,
 }
 
@@ -112,32 +243,40 @@ impl RenderAsset for GizmoBuffers {
         source_asset: Self::SourceAsset,
         render_device: &mut SystemParamItem<Self::Param>,
     ) -> Result<Self, PrepareAssetError<Self::SourceAsset>> {
-        let position_buffer_data = cast_slice(&source_asset.0.vertices);
+        let position_buffer_data = cast_slice(&source_asset.vertices);
         let position_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
             usage: BufferUsages::VERTEX,
             label: Some("TransformGizmo Position Buffer"),
             contents: position_buffer_data,
         });
 
-        let index_buffer_data = cast_slice(&source_asset.0.indices);
+        let index_buffer_data = cast_slice(&source_asset.indices);
         let index_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
             usage: BufferUsages::INDEX,
             label: Some("TransformGizmo Index Buffer"),
             contents: index_buffer_data,
         });
 
-        let color_buffer_data = cast_slice(&source_asset.0.colors);
+        let color_buffer_data = cast_slice(&source_asset.colors);
         let color_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
             usage: BufferUsages::VERTEX,
             label: Some("TransformGizmo Color Buffer"),
             contents: color_buffer_data,
         });
 
+        let uv_buffer_data = cast_slice(&source_asset.uvs);
+        let uv_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            usage: BufferUsages::VERTEX,
+            label: Some("TransformGizmo UV Buffer"),
+            contents: uv_buffer_data,
+        });
+
         Ok(Self {
             index_buffer,
             position_buffer,
             color_buffer,
-            index_count: source_asset.0.indices.len() as u32,
+            uv_buffer,
+            index_count: source_asset.indices.len() as u32,
         })
     }
 }
@@ -168,6 +307,7 @@ impl<P: PhaseItem> RenderCommand<P> for DrawTransformGizmo {
         pass.set_index_buffer(gizmo.index_buffer.slice(..), 0, IndexFormat::Uint32);
         pass.set_vertex_buffer(0, gizmo.position_buffer.slice(..));
         pass.set_vertex_buffer(1, gizmo.color_buffer.slice(..));
+        pass.set_vertex_buffer(2, gizmo.uv_buffer.slice(..));
 
         pass.draw_indexed(0..gizmo.index_count, 0, 0..1);
 
@@ -175,15 +315,55 @@ impl<P: PhaseItem> RenderCommand<P> for DrawTransformGizmo {
     }
 }
 
+struct SetGizmoFontBindGroup<const I: usize>;
+
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetGizmoFontBindGroup<I> {
+    type ViewQuery = ();
+    type ItemQuery = ();
+    type Param = Option<SRes<GizmoFontBindGroup>>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, Self::ViewQuery>,
+        _entity: Option<ROQueryItem<'w, Self::ItemQuery>>,
+        bind_group: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(bind_group) = bind_group else {
+            return RenderCommandResult::Failure;
+        };
+
+        pass.set_bind_group(I, &bind_group.into_inner().0, &[]);
+
+        RenderCommandResult::Success
+    }
+}
+
 #[derive(Clone, Resource)]
 struct TransformGizmoPipeline {
     mesh_pipeline: MeshPipeline,
+    font_layout: BindGroupLayout,
 }
 
 impl FromWorld for TransformGizmoPipeline {
     fn from_world(render_world: &mut World) -> Self {
+        let font_layout = render_world
+            .resource::<RenderDevice>()
+            .create_bind_group_layout(
+                "TransformGizmo Font Bind Group Layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::FRAGMENT,
+                    (
+                        texture_2d(TextureSampleType::Float { filterable: true }),
+                        sampler(SamplerBindingType::Filtering),
+                    ),
+                ),
+            );
+
         Self {
             mesh_pipeline: render_world.resource::<MeshPipeline>().clone(),
+            font_layout,
         }
     }
 }
@@ -243,6 +423,15 @@ impl SpecializedRenderPipeline for TransformGizmoPipeline {
                             shader_location: 1,
                         }],
                     },
+                    VertexBufferLayout {
+                        array_stride: VertexFormat::Float32x2.size(),
+                        step_mode: VertexStepMode::Vertex,
+                        attributes: vec![VertexAttribute {
+                            format: VertexFormat::Float32x2,
+                            offset: 0,
+                            shader_location: 2,
+                        }],
+                    },
                 ],
             },
             fragment: Some(FragmentState {
@@ -255,7 +444,7 @@ impl SpecializedRenderPipeline for TransformGizmoPipeline {
                     write_mask: ColorWrites::ALL,
                 })],
             }),
-            layout: vec![view_layout],
+            layout: vec![view_layout, self.font_layout.clone()],
             primitive: PrimitiveState {
                 topology: PrimitiveTopology::TriangleList,
                 cull_mode: None,
@@ -278,7 +467,12 @@ impl SpecializedRenderPipeline for TransformGizmoPipeline {
     }
 }
 
-type DrawGizmo = (SetItemPipeline, SetMeshViewBindGroup<0>, DrawTransformGizmo);
+type DrawGizmo = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetGizmoFontBindGroup<1>,
+    DrawTransformGizmo,
+);
 
 #[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn queue_transform_gizmos(
@@ -44,9 +44,10 @@
 //! ```
 //!
 //!
-use egui::{epaint::Vertex, Mesh, PointerButton, Pos2, Rgba, Sense, Ui, Vec2};
+use egui::{epaint::Vertex, Align2, FontId, Mesh, PointerButton, Pos2, Rgba, Sense, Ui, Vec2};
 
 use transform_gizmo::math::Transform;
+use transform_gizmo::navigation::{ViewCube, ViewCubeResult};
 pub use transform_gizmo::*;
 pub mod prelude;
 
@@ -54,6 +55,10 @@ pub trait GizmoExt {
     /// Interact with the gizmo and draw it to Ui.
     ///
     /// Returns result of the gizmo interaction.
+    ///
+    /// Pressing Escape cancels the active interaction. The latest result is then
+    /// returned with the transforms from the start of the interaction, and
+    /// [`Gizmo::drag_phase`] is [`GizmoDragPhase::Cancelled`].
     fn interact(&mut self, ui: &Ui, targets: &[Transform])
         -> Option<(GizmoResult, Vec<Transform>)>;
 }
@@ -91,6 +96,34 @@ impl GizmoExt for Gizmo {
         );
         let hovered = interaction.hovered();
 
+        if ui.input(|input| input.key_pressed(egui::Key::Escape)) {
+            if let Some(start_targets) = self.cancel() {
+                paint(ui, egui_viewport, self.draw());
+                return self.last_result().map(|result| (result, start_targets));
+            }
+        }
+
+        // Forward typed values to the gizmo. They are ignored unless the gizmo is active.
+        ui.input(|input| {
+            for event in &input.events {
+                match event {
+                    egui::Event::Text(text) => {
+                        for c in text.chars() {
+                            self.type_key(GizmoKey::Char(c));
+                        }
+                    }
+                    egui::Event::Key {
+                        key, pressed: true, ..
+                    } => match key {
+                        egui::Key::Backspace => self.type_key(GizmoKey::Backspace),
+                        egui::Key::Enter => self.type_key(GizmoKey::Enter),
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+        });
+
         let gizmo_result = self.update(
             GizmoInteraction {
                 cursor_pos: (cursor_pos.x, cursor_pos.y),
@@ -102,23 +135,86 @@ impl GizmoExt for Gizmo {
             targets,
         );
 
-        let draw_data = self.draw();
-
-        egui::Painter::new(ui.ctx().clone(), ui.layer_id(), egui_viewport).add(Mesh {
-            indices: draw_data.indices,
-            vertices: draw_data
-                .vertices
-                .into_iter()
-                .zip(draw_data.colors)
-                .map(|(pos, [r, g, b, a])| Vertex {
-                    pos: pos.into(),
-                    uv: Pos2::default(),
-                    color: Rgba::from_rgba_premultiplied(r, g, b, a).into(),
-                })
-                .collect(),
-            ..Default::default()
-        });
+        paint(ui, egui_viewport, self.draw());
 
         gizmo_result
     }
 }
+
+/// Draws a [`ViewCube`] in the top right corner of the viewport and reports clicks on it.
+///
+/// The viewport defaults to the clip rect of the Ui. Update the view matrix of the
+/// view cube together with the camera, and rotate the camera to
+/// [`ViewCubeResult::view_rotation`] when it is clicked.
+pub trait ViewCubeExt {
+    /// Interact with the view cube and draw it to Ui.
+    ///
+    /// Returns the result if the view cube was clicked this frame.
+    fn interact(&mut self, ui: &Ui) -> Option<ViewCubeResult>;
+}
+
+impl ViewCubeExt for ViewCube {
+    fn interact(&mut self, ui: &Ui) -> Option<ViewCubeResult> {
+        let cursor_pos = ui
+            .input(|input| input.pointer.hover_pos())
+            .unwrap_or_default();
+
+        let mut viewport = self.config().viewport;
+        if !viewport.is_finite() {
+            viewport = ui.clip_rect();
+        }
+
+        self.update_config(GizmoConfig {
+            viewport,
+            pixels_per_point: ui.ctx().pixels_per_point(),
+            ..*self.config()
+        });
+
+        let interaction = ui.interact(
+            Rect::from_center_size(cursor_pos, Vec2::splat(1.0)),
+            ui.id().with("_view_cube_interaction"),
+            Sense::click(),
+        );
+
+        let result = self.update(GizmoInteraction {
+            cursor_pos: (cursor_pos.x, cursor_pos.y),
+            hovered: interaction.hovered(),
+            drag_started: ui.input(|input| input.pointer.button_pressed(PointerButton::Primary)),
+            dragging: ui.input(|input| input.pointer.button_down(PointerButton::Primary)),
+        });
+
+        paint(ui, viewport, self.draw());
+
+        result
+    }
+}
+
+fn paint(ui: &Ui, viewport: Rect, draw_data: GizmoDrawData) {
+    let painter = egui::Painter::new(ui.ctx().clone(), ui.layer_id(), viewport);
+
+    painter.add(Mesh {
+        indices: draw_data.indices,
+        vertices: draw_data
+            .vertices
+            .into_iter()
+            .zip(draw_data.colors)
+            .map(|(pos, [r, g, b, a])| Vertex {
+                pos: pos.into(),
+                uv: Pos2::default(),
+                color: Rgba::from_rgba_premultiplied(r, g, b, a).into(),
+            })
+            .collect(),
+        ..Default::default()
+    });
+
+    for text in draw_data.texts {
+        let [r, g, b, a] = text.color;
+        painter.text(
+            text.position.into(),
+            Align2::CENTER_CENTER,
+            text.text,
+            FontId::proportional(text.size),
+            Rgba::from_rgba_premultiplied(r, g, b, a).into(),
+        );
+    }
+}
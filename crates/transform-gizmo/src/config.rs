@@ -2,11 +2,13 @@ use std::ops::{Deref, DerefMut};
 
 pub use ecolor::Color32;
 
-use emath::Rect;
+use emath::{Pos2, Rect};
 use enumset::{enum_set, EnumSet, EnumSetType};
+use glam::{DAffine3, EulerRot};
 
 use crate::math::{
-    screen_to_world, world_to_screen, DMat4, DQuat, DVec3, DVec4, Transform, Vec4Swizzles,
+    decompose_affine, screen_to_world, world_to_screen, DMat3, DMat4, DQuat, DVec3, DVec4,
+    Transform, Vec3, Vec4Swizzles,
 };
 
 /// The default snapping distance for rotation in radians
@@ -15,35 +17,127 @@ pub const DEFAULT_SNAP_ANGLE: f32 = std::f32::consts::PI / 32.0;
 pub const DEFAULT_SNAP_DISTANCE: f32 = 0.1;
 /// The default snapping distance for scale
 pub const DEFAULT_SNAP_SCALE: f32 = 0.1;
+/// The default screen space radius for snapping to snap targets, in pixels
+pub const DEFAULT_SNAP_TARGET_RADIUS: f32 = 10.0;
 
 /// Configuration of a gizmo.
 ///
 /// Defines how the gizmo is drawn to the screen and
 /// how it can be interacted with.
+///
+/// Both perspective and orthographic projections are supported.
+/// With an orthographic projection the gizmo keeps its size on screen
+/// regardless of its depth, and pointer rays are parallel to the view direction.
+///
+/// # Examples
+///
+/// ```
+/// # use transform_gizmo::math::{DMat4, DQuat, DVec3, Transform};
+/// # use transform_gizmo::config::{ClipSpace, DepthRange};
+/// # use transform_gizmo::{Gizmo, GizmoConfig, GizmoInteraction};
+/// # let viewport = emath::Rect::from_min_size(Default::default(), emath::vec2(800.0, 600.0));
+/// let mut gizmo = Gizmo::new(GizmoConfig {
+///     view_matrix: DMat4::look_at_rh(DVec3::Y * 10.0, DVec3::ZERO, DVec3::Z).into(),
+///     projection_matrix: DMat4::orthographic_rh(-8.0, 8.0, -6.0, 6.0, 0.1, 100.0).into(),
+///     clip_space: ClipSpace {
+///         depth_range: DepthRange::ZeroToOne,
+///         ..Default::default()
+///     },
+///     viewport,
+///     ..Default::default()
+/// });
+///
+/// // The gizmo is drawn with the same size at any depth
+/// let widths = [-5.0, 5.0].map(|depth| {
+///     let translation = DVec3::Y * depth;
+///     let target = Transform::from_scale_rotation_translation(DVec3::ONE, DQuat::IDENTITY, translation);
+///     gizmo.update(GizmoInteraction::default(), &[target]);
+///
+///     let vertices = gizmo.draw().vertices;
+///     let xs = vertices.iter().map(|vertex| vertex[0]);
+///     xs.clone().fold(f32::MIN, f32::max) - xs.fold(f32::MAX, f32::min)
+/// });
+/// assert!((widths[0] - widths[1]).abs() < 1e-3);
+/// ```
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct GizmoConfig {
     /// View matrix for the gizmo, aligning it with the camera's viewpoint.
     pub view_matrix: mint::RowMatrix4<f64>,
     /// Projection matrix for the gizmo, determining how it is projected onto the screen.
     pub projection_matrix: mint::RowMatrix4<f64>,
+    /// Clip space conventions of the projection matrix.
+    pub clip_space: ClipSpace,
     /// Screen area where the gizmo is displayed.
     pub viewport: Rect,
+    /// Offset of the origin used for the gizmo's internal view and projection math.
+    /// The view matrix and targets are still given in world space.
+    ///
+    /// Setting this to the camera position keeps picking precise in large worlds,
+    /// far away from the world origin.
+    pub origin_offset: mint::Vector3<f64>,
     /// The gizmo's operation modes.
     pub modes: EnumSet<GizmoMode>,
     /// If set, this mode is forced active and other modes are disabled
     pub mode_override: Option<GizmoMode>,
     /// Determines the gizmo's orientation relative to global or local axes.
     pub orientation: GizmoOrientation,
+    /// Rotation of the targets' parent in world space.
+    /// Used with [`GizmoOrientation::Parent`].
+    pub parent_rotation: mint::Quaternion<f64>,
+    /// Rotation of the gizmo axes in world space.
+    /// Used with [`GizmoOrientation::Custom`].
+    pub custom_orientation: mint::Quaternion<f64>,
     /// Pivot point for transformations
     pub pivot_point: TransformPivotPoint,
+    /// Position of the pivot in world space, such as a 3D cursor.
+    /// Used with [`TransformPivotPoint::Point`].
+    pub pivot_position: mint::Vector3<f64>,
+    /// Gizmo axes along which translation is locked.
+    /// Handles of locked axes are drawn with [`GizmoVisuals::disabled_color`]
+    /// and cannot be interacted with. Plane and view handles move only along unlocked axes.
+    pub locked_translation: EnumSet<GizmoDirection>,
+    /// Gizmo axes around which rotation is locked.
+    /// Handles of locked axes are drawn with [`GizmoVisuals::disabled_color`]
+    /// and cannot be interacted with.
+    pub locked_rotation: EnumSet<GizmoDirection>,
+    /// Gizmo axes along which scale is locked.
+    /// Handles of locked axes are drawn with [`GizmoVisuals::disabled_color`]
+    /// and cannot be interacted with. Plane and uniform handles scale only unlocked axes.
+    pub locked_scale: EnumSet<GizmoDirection>,
+    /// Bounding box of the targets in the local space of the last target.
+    /// Used by the bounds modes, see [`GizmoMode::all_bounds`].
+    pub bounds: GizmoBounds,
+    /// If true, dragging the gizmo moves only its pivot point and the targets
+    /// are left in place. Only translation subgizmos are used in this mode.
+    /// The edited pivot is kept as an offset, see [`crate::Gizmo::pivot_offset`].
+    pub edit_pivot: bool,
     /// Toggles snapping to predefined increments during transformations for precision.
     pub snapping: bool,
+    /// Whether snapping is relative to the start of the interaction or to the world grid.
+    pub snap_mode: SnapMode,
     /// Angle increment for snapping rotations, in radians.
     pub snap_angle: f32,
     /// Distance increment for snapping translations.
     pub snap_distance: f32,
     /// Scale increment for snapping scalings.
     pub snap_scale: f32,
+    /// Angle increments for snapping rotations around each gizmo axis, in radians.
+    /// Overrides `snap_angle` for rotations around the X, Y and Z axes.
+    pub snap_angle_per_axis: Option<mint::Vector3<f32>>,
+    /// Distance increments for snapping translations along each gizmo axis.
+    /// Overrides `snap_distance`.
+    pub snap_distance_per_axis: Option<mint::Vector3<f32>>,
+    /// Scale increments for snapping scalings along each gizmo axis.
+    /// Overrides `snap_scale`.
+    pub snap_scale_per_axis: Option<mint::Vector3<f32>>,
+    /// Local axis of the targets that is aligned to the surface normal
+    /// in [`GizmoMode::TranslateSurface`]. [`GizmoDirection::View`] is ignored.
+    pub surface_align_axis: Option<GizmoDirection>,
+    /// How close to the dragged pivot a snap target needs to be on screen, in pixels.
+    /// See [`crate::snapping::SnapTargetProvider`].
+    pub snap_target_radius: f32,
     /// Visual settings for the gizmo, affecting appearance and visibility.
     pub visuals: GizmoVisuals,
     /// Ratio of window's physical size to logical size.
@@ -55,15 +149,31 @@ impl Default for GizmoConfig {
         Self {
             view_matrix: DMat4::IDENTITY.into(),
             projection_matrix: DMat4::IDENTITY.into(),
+            clip_space: ClipSpace::default(),
             viewport: Rect::NOTHING,
-            modes: GizmoMode::all(),
+            origin_offset: DVec3::ZERO.into(),
+            modes: GizmoMode::all() - GizmoMode::all_bounds(),
             mode_override: None,
             orientation: GizmoOrientation::default(),
+            parent_rotation: DQuat::IDENTITY.into(),
+            custom_orientation: DQuat::IDENTITY.into(),
             pivot_point: TransformPivotPoint::default(),
+            pivot_position: DVec3::ZERO.into(),
+            locked_translation: EnumSet::empty(),
+            locked_rotation: EnumSet::empty(),
+            locked_scale: EnumSet::empty(),
+            bounds: GizmoBounds::default(),
+            edit_pivot: false,
             snapping: false,
+            snap_mode: SnapMode::default(),
             snap_angle: DEFAULT_SNAP_ANGLE,
             snap_distance: DEFAULT_SNAP_DISTANCE,
             snap_scale: DEFAULT_SNAP_SCALE,
+            snap_angle_per_axis: None,
+            snap_distance_per_axis: None,
+            snap_scale_per_axis: None,
+            snap_target_radius: DEFAULT_SNAP_TARGET_RADIUS,
+            surface_align_axis: None,
             visuals: GizmoVisuals::default(),
             pixels_per_point: 1.0,
         }
@@ -86,9 +196,43 @@ impl GizmoConfig {
         DVec4::from(self.view_matrix.x).xyz()
     }
 
-    /// Whether local orientation is used
-    pub(crate) fn local_space(&self) -> bool {
-        self.orientation() == GizmoOrientation::Local
+    /// Angle increments for snapping rotations around each gizmo axis
+    pub(crate) fn snap_angles(&self) -> DVec3 {
+        per_axis(self.snap_angle_per_axis, self.snap_angle)
+    }
+
+    /// Distance increments for snapping translations along each gizmo axis
+    pub(crate) fn snap_distances(&self) -> DVec3 {
+        per_axis(self.snap_distance_per_axis, self.snap_distance)
+    }
+
+    /// Scale increments for snapping scalings along each gizmo axis
+    pub(crate) fn snap_scales(&self) -> DVec3 {
+        per_axis(self.snap_scale_per_axis, self.snap_scale)
+    }
+
+    /// Whether the gizmo axes are rotated, i.e. not aligned to world space
+    pub(crate) fn rotated_axes(&self) -> bool {
+        self.orientation() != GizmoOrientation::Global
+    }
+
+    /// Rotation of the gizmo axes, if the orientation does not depend on the targets
+    pub(crate) fn orientation_rotation(&self) -> Option<DQuat> {
+        match self.orientation() {
+            GizmoOrientation::Global | GizmoOrientation::Local | GizmoOrientation::Gimbal(_) => {
+                None
+            }
+            GizmoOrientation::View => Some(
+                DQuat::from_mat3(&DMat3::from_cols(
+                    self.view_right(),
+                    self.view_up(),
+                    self.view_forward(),
+                ))
+                .normalize(),
+            ),
+            GizmoOrientation::Parent => Some(DQuat::from(self.parent_rotation).normalize()),
+            GizmoOrientation::Custom => Some(DQuat::from(self.custom_orientation).normalize()),
+        }
     }
 
     /// Transform orientation of the gizmo
@@ -96,10 +240,25 @@ impl GizmoConfig {
         self.orientation
     }
 
+    /// Axes that are locked for given kind of transformation
+    pub fn locked_axes(&self, kind: GizmoModeKind) -> EnumSet<GizmoDirection> {
+        match kind {
+            GizmoModeKind::Rotate | GizmoModeKind::Arcball => self.locked_rotation,
+            GizmoModeKind::Translate => self.locked_translation,
+            GizmoModeKind::Scale | GizmoModeKind::Bounds => self.locked_scale,
+        }
+    }
+
+    /// Whether all axes of given mode are locked
+    pub fn is_mode_locked(&self, mode: GizmoMode) -> bool {
+        mode.axes().is_subset(self.locked_axes(mode.kind()))
+    }
+
     /// Whether the modes have changed, compared to given other config
     pub(crate) fn modes_changed(&self, other: &Self) -> bool {
         (self.modes != other.modes && self.mode_override.is_none())
             || (self.mode_override != other.mode_override)
+            || (self.edit_pivot != other.edit_pivot)
     }
 }
 
@@ -114,6 +273,10 @@ pub(crate) struct PreparedGizmoConfig {
     pub(crate) scale: DVec3,
     /// Combined view-projection matrix
     pub(crate) view_projection: DMat4,
+    /// Combined view-projection matrix, relative to [`GizmoConfig::origin_offset`]
+    pub(crate) rebased_view_projection: DMat4,
+    /// Inverse of the rebased view-projection matrix
+    pub(crate) inverse_rebased_view_projection: DMat4,
     /// Model matrix from targets
     pub(crate) model_matrix: DMat4,
     /// Combined model-view-projection matrix
@@ -122,10 +285,20 @@ pub(crate) struct PreparedGizmoConfig {
     pub(crate) scale_factor: f32,
     /// How close the mouse pointer needs to be to a subgizmo before it is focused
     pub(crate) focus_distance: f32,
+    /// Whether orthographic projection is used
+    pub(crate) orthographic: bool,
     /// Whether left-handed projection is used
     pub(crate) left_handed: bool,
     /// Direction from the camera to the gizmo in world space
     pub(crate) eye_to_model_dir: DVec3,
+    /// Offset of the pivot point, in the local space of the last target
+    pub(crate) pivot_offset: DVec3,
+    /// Translation of the last target
+    pub(crate) active_translation: DVec3,
+    /// Rotation of the last target
+    pub(crate) active_rotation: DQuat,
+    /// Scale of the last target
+    pub(crate) active_scale: DVec3,
 }
 
 impl Deref for PreparedGizmoConfig {
@@ -144,12 +317,19 @@ impl DerefMut for PreparedGizmoConfig {
 
 impl PreparedGizmoConfig {
     pub(crate) fn update_for_config(&mut self, config: GizmoConfig) {
-        let projection_matrix = DMat4::from(config.projection_matrix);
+        // The projection is converted to the OpenGL conventions used internally
+        let projection_matrix =
+            config.clip_space.normalizing_matrix() * DMat4::from(config.projection_matrix);
         let view_matrix = DMat4::from(config.view_matrix);
 
         let view_projection = projection_matrix * view_matrix;
+        let rebased_view_projection = projection_matrix
+            * (view_matrix * DMat4::from_translation(config.origin_offset.into()));
 
-        let left_handed = if projection_matrix.z_axis.w == 0.0 {
+        // Orthographic projections keep w constant, so the handedness is
+        // given by the direction in which depth increases towards the far plane.
+        let orthographic = projection_matrix.z_axis.w == 0.0;
+        let left_handed = if orthographic {
             projection_matrix.z_axis.z > 0.0
         } else {
             projection_matrix.z_axis.w > 0.0
@@ -157,6 +337,9 @@ impl PreparedGizmoConfig {
 
         self.config = config;
         self.view_projection = view_projection;
+        self.rebased_view_projection = rebased_view_projection;
+        self.inverse_rebased_view_projection = rebased_view_projection.inverse();
+        self.orthographic = orthographic;
         self.left_handed = left_handed;
 
         self.update_transform(Transform {
@@ -170,12 +353,21 @@ impl PreparedGizmoConfig {
         let mut scale = DVec3::ZERO;
         let mut translation = DVec3::ZERO;
         let mut rotation = DQuat::IDENTITY;
+        let mut min_translation = DVec3::MAX;
+        let mut max_translation = DVec3::MIN;
+        let mut last_translation = DVec3::ZERO;
+        let mut last_scale = DVec3::ONE;
 
         let mut target_count = 0;
         for target in targets {
             scale += DVec3::from(target.scale);
             translation += DVec3::from(target.translation);
             rotation = DQuat::from(target.rotation);
+            last_scale = DVec3::from(target.scale);
+
+            last_translation = DVec3::from(target.translation);
+            min_translation = min_translation.min(last_translation);
+            max_translation = max_translation.max(last_translation);
 
             target_count += 1;
         }
@@ -185,6 +377,29 @@ impl PreparedGizmoConfig {
         } else {
             translation /= target_count as f64;
             scale /= target_count as f64;
+
+            translation = match self.pivot_point {
+                TransformPivotPoint::MedianPoint | TransformPivotPoint::IndividualOrigins => {
+                    translation
+                }
+                TransformPivotPoint::BoundingBoxCenter => (min_translation + max_translation) / 2.0,
+                TransformPivotPoint::ActiveElement => last_translation,
+                TransformPivotPoint::Point => self.pivot_position.into(),
+            };
+
+            if self.pivot_point != TransformPivotPoint::IndividualOrigins {
+                translation += rotation * self.pivot_offset;
+            }
+        }
+
+        self.set_active_target(Transform::from_scale_rotation_translation(
+            last_scale,
+            rotation,
+            last_translation,
+        ));
+
+        if let Some(orientation_rotation) = self.orientation_rotation() {
+            rotation = orientation_rotation;
         }
 
         self.update_transform(Transform {
@@ -194,32 +409,73 @@ impl PreparedGizmoConfig {
         });
     }
 
+    /// Sets the transform of the last target, which the bounding box follows.
+    pub(crate) fn set_active_target(&mut self, target: Transform) {
+        self.active_translation = target.translation.into();
+        self.active_rotation = target.rotation.into();
+        self.active_scale = target.scale.into();
+    }
+
     pub(crate) fn update_transform(&mut self, transform: Transform) {
         self.translation = transform.translation.into();
         self.rotation = transform.rotation.into();
         self.scale = transform.scale.into();
         self.model_matrix =
             DMat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation);
-        self.mvp = self.view_projection * self.model_matrix;
 
-        self.scale_factor = self.mvp.as_ref()[15] as f32
+        // The model is placed relative to the origin offset to retain precision
+        let rebased_translation = self.translation - DVec3::from(self.config.origin_offset);
+        self.mvp = self.rebased_view_projection
+            * DMat4::from_scale_rotation_translation(
+                self.scale,
+                self.rotation,
+                rebased_translation,
+            );
+
+        // The w of the gizmo origin is its view depth for perspective projections
+        // and constant for orthographic ones, keeping the gizmo size constant on screen.
+        self.scale_factor = self.mvp.w_axis.w as f32
             / self.projection_matrix.x.x as f32
             / self.config.viewport.width()
             * 2.0;
 
-        let gizmo_screen_pos =
-            world_to_screen(self.config.viewport, self.mvp, self.translation).unwrap_or_default();
+        self.focus_distance = self.scale_factor * (self.config.visuals.stroke_width / 2.0 + 5.0);
 
-        let gizmo_view_near = screen_to_world(
-            self.config.viewport,
-            self.view_projection.inverse(),
-            gizmo_screen_pos,
-            -1.0,
-        );
+        self.eye_to_model_dir = if self.orthographic {
+            self.view_backward()
+        } else {
+            let gizmo_screen_pos = world_to_screen(
+                self.config.viewport,
+                self.rebased_view_projection,
+                rebased_translation,
+            )
+            .unwrap_or_default();
 
-        self.focus_distance = self.scale_factor * (self.config.visuals.stroke_width / 2.0 + 5.0);
+            let gizmo_view_near = self.screen_to_world(gizmo_screen_pos, -1.0);
+
+            (gizmo_view_near - self.translation).normalize_or_zero()
+        };
+    }
 
-        self.eye_to_model_dir = (gizmo_view_near - self.translation).normalize_or_zero();
+    /// Direction from the scene towards the camera, in world space
+    pub(crate) fn view_backward(&self) -> DVec3 {
+        let forward = self.view_forward().normalize_or_zero();
+        if self.left_handed {
+            -forward
+        } else {
+            forward
+        }
+    }
+
+    /// World space position of a screen position, at given depth in normalized device coordinates.
+    /// Computed relative to [`GizmoConfig::origin_offset`] to retain precision.
+    pub(crate) fn screen_to_world(&self, pos: Pos2, z: f64) -> DVec3 {
+        screen_to_world(
+            self.config.viewport,
+            self.inverse_rebased_view_projection,
+            pos,
+            z,
+        ) + DVec3::from(self.config.origin_offset)
     }
 
     pub(crate) fn as_transform(&self) -> Transform {
@@ -231,8 +487,16 @@ impl PreparedGizmoConfig {
     }
 }
 
+fn per_axis(values: Option<mint::Vector3<f32>>, value: f32) -> DVec3 {
+    values.map_or(DVec3::splat(value as f64), |values| {
+        Vec3::from(values).as_dvec3()
+    })
+}
+
 /// Operation mode of a gizmo.
 #[derive(Debug, EnumSetType, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", enumset(serialize_repr = "list"))]
 pub enum GizmoMode {
     /// Rotate around the X axis
     RotateX,
@@ -256,6 +520,9 @@ pub enum GizmoMode {
     TranslateYZ,
     /// Translate along the view forward axis
     TranslateView,
+    /// Translate along the scene surfaces under the pointer.
+    /// Requires [`crate::Gizmo::set_surface_raycast`].
+    TranslateSurface,
     /// Scale along the X axis
     ScaleX,
     /// Scale along the Y axis
@@ -272,6 +539,12 @@ pub enum GizmoMode {
     ScaleUniform,
     /// Rotate using an arcball (trackball)
     Arcball,
+    /// Scale from the opposite face by dragging a face of the bounding box
+    BoundsFace,
+    /// Scale from the opposite edge by dragging an edge of the bounding box
+    BoundsEdge,
+    /// Scale from the opposite corner by dragging a corner of the bounding box
+    BoundsCorner,
 }
 
 impl GizmoMode {
@@ -295,6 +568,7 @@ impl GizmoMode {
                 | Self::TranslateXZ
                 | Self::TranslateYZ
                 | Self::TranslateView
+                | Self::TranslateSurface
         )
     }
 
@@ -311,6 +585,11 @@ impl GizmoMode {
         )
     }
 
+    /// All bounding box scaling modes
+    pub const fn all_bounds() -> EnumSet<Self> {
+        enum_set!(Self::BoundsFace | Self::BoundsEdge | Self::BoundsCorner)
+    }
+
     /// Is this mode for rotation
     pub fn is_rotate(&self) -> bool {
         self.kind() == GizmoModeKind::Rotate
@@ -338,10 +617,14 @@ impl GizmoMode {
             Self::RotateZ | Self::TranslateZ | Self::ScaleZ => {
                 enum_set!(GizmoDirection::Z)
             }
-            Self::RotateView | Self::TranslateView => {
+            Self::RotateView | Self::TranslateView | Self::TranslateSurface => {
                 enum_set!(GizmoDirection::View)
             }
-            Self::ScaleUniform | Self::Arcball => {
+            Self::ScaleUniform
+            | Self::Arcball
+            | Self::BoundsFace
+            | Self::BoundsEdge
+            | Self::BoundsCorner => {
                 enum_set!(GizmoDirection::X | GizmoDirection::Y | GizmoDirection::Z)
             }
             Self::TranslateXY | Self::ScaleXY => {
@@ -375,7 +658,8 @@ impl GizmoMode {
             | Self::TranslateXY
             | Self::TranslateXZ
             | Self::TranslateYZ
-            | Self::TranslateView => GizmoModeKind::Translate,
+            | Self::TranslateView
+            | Self::TranslateSurface => GizmoModeKind::Translate,
             Self::ScaleX
             | Self::ScaleY
             | Self::ScaleZ
@@ -384,6 +668,7 @@ impl GizmoMode {
             | Self::ScaleYZ
             | Self::ScaleUniform => GizmoModeKind::Scale,
             Self::Arcball => GizmoModeKind::Arcball,
+            Self::BoundsFace | Self::BoundsEdge | Self::BoundsCorner => GizmoModeKind::Bounds,
         }
     }
 }
@@ -394,29 +679,304 @@ pub enum GizmoModeKind {
     Translate,
     Scale,
     Arcball,
+    Bounds,
+}
+
+/// Axis-aligned box.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GizmoBounds {
+    /// Minimum corner of the box
+    pub min: mint::Vector3<f64>,
+    /// Maximum corner of the box
+    pub max: mint::Vector3<f64>,
+}
+
+impl Default for GizmoBounds {
+    fn default() -> Self {
+        Self {
+            min: DVec3::splat(-0.5).into(),
+            max: DVec3::splat(0.5).into(),
+        }
+    }
+}
+
+/// Range limits for the transform of a single target.
+///
+/// Limits are applied to the transforms returned by [`crate::Gizmo::update`]
+/// and the other update methods, see [`crate::Gizmo::set_target_limits`].
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct TransformLimits {
+    /// Allowed range of the translation
+    pub translation: Option<GizmoBounds>,
+    /// Minimum Euler angles of the rotation in radians, using [`Self::rotation_order`]
+    pub min_rotation: Option<mint::Vector3<f64>>,
+    /// Maximum Euler angles of the rotation in radians, using [`Self::rotation_order`]
+    pub max_rotation: Option<mint::Vector3<f64>>,
+    /// Order of the Euler angles used for the rotation limits
+    pub rotation_order: EulerOrder,
+    /// Minimum scale. Scale is always kept positive.
+    pub min_scale: Option<mint::Vector3<f64>>,
+    /// Maximum scale
+    pub max_scale: Option<mint::Vector3<f64>>,
+}
+
+impl TransformLimits {
+    /// Smallest scale allowed, regardless of [`Self::min_scale`]
+    const MIN_SCALE: f64 = 1e-4;
+
+    /// Returns the given transform clamped to these limits.
+    pub fn clamp(&self, transform: Transform) -> Transform {
+        let mut translation = DVec3::from(transform.translation);
+        if let Some(bounds) = self.translation {
+            translation = translation.clamp(bounds.min.into(), bounds.max.into());
+        }
+
+        let mut rotation = DQuat::from(transform.rotation);
+        if self.min_rotation.is_some() || self.max_rotation.is_some() {
+            let angles = self.rotation_order.angles(rotation);
+            let min = self.min_rotation.map_or(DVec3::NEG_INFINITY, DVec3::from);
+            let max = self.max_rotation.map_or(DVec3::INFINITY, DVec3::from);
+            let clamped = angles.max(min).min(max);
+
+            // Avoid drifting the rotation through the Euler conversion when already within limits
+            if clamped != angles {
+                rotation = self.rotation_order.rotation(clamped);
+            }
+        }
+
+        let min_scale = self
+            .min_scale
+            .map_or(DVec3::ZERO, DVec3::from)
+            .max(DVec3::splat(Self::MIN_SCALE));
+        let max_scale = self.max_scale.map_or(DVec3::INFINITY, DVec3::from);
+        let scale = DVec3::from(transform.scale).max(min_scale).min(max_scale);
+
+        Transform {
+            scale: scale.into(),
+            rotation: rotation.into(),
+            translation: translation.into(),
+        }
+    }
+
+    /// Returns the given affine transformation clamped to these limits.
+    ///
+    /// The limits apply to the scale, rotation and translation decomposed from the matrix.
+    /// Shear and mirroring of the matrix are kept.
+    pub(crate) fn clamp_affine(&self, affine: DAffine3) -> DAffine3 {
+        let (scale, rotation, translation) = decompose_affine(affine);
+        let clamped = self.clamp(Transform::from_scale_rotation_translation(
+            scale.abs(),
+            rotation,
+            translation,
+        ));
+
+        // Scale the matrix axes by the change of the scale, and rotate them by the change
+        // of the rotation. The decomposition is upper triangular, so the shear is kept.
+        let scale_change = DVec3::select(
+            scale.cmpeq(DVec3::ZERO),
+            DVec3::ONE,
+            DVec3::from(clamped.scale) / scale.abs(),
+        );
+        let rotation_change = DQuat::from(clamped.rotation) * rotation.inverse();
+
+        DAffine3 {
+            matrix3: DMat3::from_quat(rotation_change)
+                * affine.matrix3
+                * DMat3::from_diagonal(scale_change),
+            translation: clamped.translation.into(),
+        }
+    }
+}
+
+/// Clip space conventions of a projection matrix.
+///
+/// The default matches OpenGL, with depth from -1 to 1 and the Y axis pointing up.
+/// Direct3D, Metal, Vulkan and WebGPU use depth from 0 to 1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ClipSpace {
+    /// Range of depth values in normalized device coordinates
+    pub depth_range: DepthRange,
+    /// Whether the near plane is at the far end of the depth range,
+    /// as in reversed-Z projections
+    pub reversed_z: bool,
+    /// Whether the Y axis of normalized device coordinates points down
+    /// in the viewport, as in Vulkan
+    pub y_down: bool,
+}
+
+impl ClipSpace {
+    /// Converts clip coordinates of this convention to OpenGL clip coordinates,
+    /// with the near plane at depth -1 and the Y axis pointing up.
+    pub(crate) fn normalizing_matrix(&self) -> DMat4 {
+        // Depth is remapped as `z * depth_scale + w * depth_offset`
+        let (depth_scale, depth_offset) = match (self.depth_range, self.reversed_z) {
+            (DepthRange::NegativeOneToOne, false) => (1.0, 0.0),
+            (DepthRange::NegativeOneToOne, true) => (-1.0, 0.0),
+            (DepthRange::ZeroToOne, false) => (2.0, -1.0),
+            (DepthRange::ZeroToOne, true) => (-2.0, 1.0),
+        };
+        let y_scale = if self.y_down { -1.0 } else { 1.0 };
+
+        DMat4::from_cols(
+            DVec4::X,
+            DVec4::Y * y_scale,
+            DVec4::Z * depth_scale,
+            DVec4::new(0.0, 0.0, depth_offset, 1.0),
+        )
+    }
+}
+
+/// Range of depth values in normalized device coordinates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DepthRange {
+    /// Depth from -1 to 1, as in OpenGL
+    #[default]
+    NegativeOneToOne,
+    /// Depth from 0 to 1, as in Direct3D, Metal, Vulkan and WebGPU
+    ZeroToOne,
+}
+
+/// Determines what snapping is aligned to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMode {
+    /// Snap the change relative to the start of the interaction
+    #[default]
+    Relative,
+    /// Snap the resulting position, angle and scale to multiples of the snap increments.
+    /// With rotated gizmo axes, positions snap to a grid aligned to the gizmo axes.
+    /// Translation in the view plane is not snapped.
+    Absolute,
 }
 
-/// The point in space around which all rotations are centered.
+/// The point in space around which all rotations and scalings are centered.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TransformPivotPoint {
     /// Pivot around the median point of targets
     #[default]
     MedianPoint,
     /// Pivot around each target's own origin
     IndividualOrigins,
+    /// Pivot around the center of the bounding box of the targets' origins
+    BoundingBoxCenter,
+    /// Pivot around the origin of the active target, which is the last target
+    ActiveElement,
+    /// Pivot around [`GizmoConfig::pivot_position`]
+    Point,
 }
 
 /// Orientation of a gizmo.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GizmoOrientation {
     /// Transformation axes are aligned to world space.
     #[default]
     Global,
     /// Transformation axes are aligned to the last target's orientation.
     Local,
+    /// Transformation axes are aligned to the camera's right, up and forward axes.
+    View,
+    /// Transformation axes are aligned to [`GizmoConfig::parent_rotation`].
+    Parent,
+    /// Transformation axes are aligned to [`GizmoConfig::custom_orientation`].
+    Custom,
+    /// Rotation axes are aligned to the Euler axes of the last target,
+    /// for the given rotation order. Rotating about an axis changes only
+    /// the Euler angle of that axis. Translation and scale use local axes.
+    Gimbal(EulerOrder),
+}
+
+/// Order in which Euler angle rotations are applied.
+///
+/// For example, with [`EulerOrder::XYZ`] the rotation around the X axis
+/// is applied first and the rotation around the Z axis last.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EulerOrder {
+    #[default]
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+impl EulerOrder {
+    /// Indices of the axes, in the order the rotations are applied
+    const fn axis_indices(self) -> [usize; 3] {
+        match self {
+            Self::XYZ => [0, 1, 2],
+            Self::XZY => [0, 2, 1],
+            Self::YXZ => [1, 0, 2],
+            Self::YZX => [1, 2, 0],
+            Self::ZXY => [2, 0, 1],
+            Self::ZYX => [2, 1, 0],
+        }
+    }
+
+    /// Matching glam rotation order. Glam lists the last applied rotation first.
+    const fn euler_rot(self) -> EulerRot {
+        match self {
+            Self::XYZ => EulerRot::ZYX,
+            Self::XZY => EulerRot::YZX,
+            Self::YXZ => EulerRot::ZXY,
+            Self::YZX => EulerRot::XZY,
+            Self::ZXY => EulerRot::YXZ,
+            Self::ZYX => EulerRot::XYZ,
+        }
+    }
+
+    /// Euler angles of given rotation around the X, Y and Z axes
+    pub(crate) fn angles(self, rotation: DQuat) -> DVec3 {
+        let [first, second, third] = self.axis_indices();
+        let (third_angle, second_angle, first_angle) = rotation.to_euler(self.euler_rot());
+
+        let mut angles = DVec3::ZERO;
+        angles[first] = first_angle;
+        angles[second] = second_angle;
+        angles[third] = third_angle;
+        angles
+    }
+
+    /// Rotation from Euler angles around the X, Y and Z axes
+    pub(crate) fn rotation(self, angles: DVec3) -> DQuat {
+        let [first, second, third] = self.axis_indices();
+        DQuat::from_euler(
+            self.euler_rot(),
+            angles[third],
+            angles[second],
+            angles[first],
+        )
+    }
+
+    /// World space axes that rotate only the X, Y and Z Euler angles of given rotation,
+    /// as columns of a matrix. The axes are not necessarily orthogonal.
+    pub(crate) fn gimbal_axes(self, rotation: DQuat) -> DMat3 {
+        let [first, second, third] = self.axis_indices();
+        let (third_angle, second_angle, _) = rotation.to_euler(self.euler_rot());
+
+        let third_rotation = DQuat::from_axis_angle(DVec3::AXES[third], third_angle);
+        let second_rotation =
+            third_rotation * DQuat::from_axis_angle(DVec3::AXES[second], second_angle);
+
+        let mut axes = [DVec3::ZERO; 3];
+        axes[first] = second_rotation * DVec3::AXES[first];
+        axes[second] = third_rotation * DVec3::AXES[second];
+        axes[third] = DVec3::AXES[third];
+
+        DMat3::from_cols(axes[0], axes[1], axes[2])
+    }
 }
 
 #[derive(Debug, EnumSetType, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", enumset(serialize_repr = "list"))]
 pub enum GizmoDirection {
     /// Gizmo points in the X-direction
     X,
@@ -430,6 +990,8 @@ pub enum GizmoDirection {
 
 /// Controls the visual style of the gizmo
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct GizmoVisuals {
     /// Color of the x axis
     pub x_color: Color32,
@@ -439,16 +1001,32 @@ pub struct GizmoVisuals {
     pub z_color: Color32,
     /// Color of the forward axis
     pub s_color: Color32,
-    /// Alpha of the gizmo color when inactive
+    /// Alpha of the gizmo color when inactive or disabled
     pub inactive_alpha: f32,
-    /// Alpha of the gizmo color when highlighted/active
+    /// Alpha of the gizmo color when hovered or active
     pub highlight_alpha: f32,
-    /// Color to use for highlighted and active axes. By default, the axis color is used with `highlight_alpha`
+    /// Color of inactive axes, which are neither hovered nor dragged.
+    /// By default, the axis color is used.
+    pub inactive_color: Option<Color32>,
+    /// Color of hovered axes. By default, the axis color is used.
     pub highlight_color: Option<Color32>,
+    /// Color of the axis being dragged. By default, `highlight_color` is used.
+    pub active_color: Option<Color32>,
+    /// Color of locked axes
+    pub disabled_color: Color32,
     /// Width (thickness) of the gizmo strokes
     pub stroke_width: f32,
     /// Gizmo size in pixels
     pub gizmo_size: f32,
+    /// Whether to draw axis labels, such as "X" at arrow tips and "XY" on plane handles.
+    /// Labels are returned in [`crate::GizmoDrawData::texts`].
+    pub axis_labels: bool,
+    /// Font size of the axis labels and the value readout in pixels
+    pub label_size: f32,
+    /// Whether to draw the total of the active interaction next to the cursor,
+    /// such as the angle of a rotation. The readout is formatted
+    /// with the [`std::fmt::Display`] implementation of [`crate::GizmoResult`].
+    pub value_readout: bool,
 }
 
 impl Default for GizmoVisuals {
@@ -460,9 +1038,165 @@ impl Default for GizmoVisuals {
             s_color: Color32::from_rgb(255, 255, 255),
             inactive_alpha: 0.7,
             highlight_alpha: 1.0,
+            inactive_color: None,
             highlight_color: None,
+            active_color: None,
+            disabled_color: Color32::from_rgb(110, 110, 110),
             stroke_width: 4.0,
             gizmo_size: 75.0,
+            axis_labels: false,
+            label_size: 14.0,
+            value_readout: true,
+        }
+    }
+}
+
+impl GizmoVisuals {
+    /// Color of the given axis
+    pub fn axis_color(&self, direction: GizmoDirection) -> Color32 {
+        match direction {
+            GizmoDirection::X => self.x_color,
+            GizmoDirection::Y => self.y_color,
+            GizmoDirection::Z => self.z_color,
+            GizmoDirection::View => self.s_color,
+        }
+    }
+
+    /// Color of a handle in the given state, based on the color of its axis
+    pub fn state_color(&self, axis_color: Color32, state: GizmoHandleState) -> Color32 {
+        match state {
+            GizmoHandleState::Inactive => self
+                .inactive_color
+                .unwrap_or(axis_color)
+                .linear_multiply(self.inactive_alpha),
+            GizmoHandleState::Hovered => self
+                .highlight_color
+                .unwrap_or(axis_color)
+                .linear_multiply(self.highlight_alpha),
+            GizmoHandleState::Active => self
+                .active_color
+                .or(self.highlight_color)
+                .unwrap_or(axis_color)
+                .linear_multiply(self.highlight_alpha),
+            GizmoHandleState::Disabled => self.disabled_color.linear_multiply(self.inactive_alpha),
+        }
+    }
+}
+
+/// Interaction state of a gizmo handle, which determines its color.
+/// See [`GizmoVisuals::state_color`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GizmoHandleState {
+    /// The handle is neither hovered nor dragged
+    Inactive,
+    /// The handle is under the pointer
+    Hovered,
+    /// The handle is being dragged
+    Active,
+    /// The handle is locked and cannot be interacted with
+    Disabled,
+}
+
+/// Named presets of [`GizmoVisuals`].
+///
+/// # Examples
+///
+/// ```
+/// # use transform_gizmo::config::{GizmoConfig, GizmoTheme};
+/// let config = GizmoConfig {
+///     visuals: GizmoTheme::ColorBlindSafe.visuals(),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GizmoTheme {
+    /// Default visuals of the gizmo
+    #[default]
+    Default,
+    /// Axis colors similar to Blender
+    Blender,
+    /// Axis colors similar to Unity, with yellow highlights
+    Unity,
+    /// Axis colors similar to Unreal Engine, with yellow highlights
+    Unreal,
+    /// Saturated, opaque colors and thicker strokes
+    HighContrast,
+    /// Axis colors from the Okabe-Ito palette, which are distinguishable
+    /// with the common forms of color vision deficiency
+    ColorBlindSafe,
+}
+
+impl GizmoTheme {
+    /// All presets, in the order they are declared
+    pub const ALL: [Self; 6] = [
+        Self::Default,
+        Self::Blender,
+        Self::Unity,
+        Self::Unreal,
+        Self::HighContrast,
+        Self::ColorBlindSafe,
+    ];
+
+    /// Visuals of this preset. Sizes and stroke widths are the defaults,
+    /// except for [`GizmoTheme::HighContrast`]. Axis labels are enabled
+    /// for [`GizmoTheme::HighContrast`] and [`GizmoTheme::ColorBlindSafe`].
+    pub fn visuals(self) -> GizmoVisuals {
+        let default = GizmoVisuals::default();
+
+        match self {
+            Self::Default => default,
+            Self::Blender => GizmoVisuals {
+                x_color: Color32::from_rgb(255, 51, 82),
+                y_color: Color32::from_rgb(139, 220, 0),
+                z_color: Color32::from_rgb(40, 144, 255),
+                inactive_alpha: 0.8,
+                highlight_color: Some(Color32::from_rgb(255, 255, 255)),
+                ..default
+            },
+            Self::Unity => GizmoVisuals {
+                x_color: Color32::from_rgb(219, 62, 29),
+                y_color: Color32::from_rgb(154, 243, 72),
+                z_color: Color32::from_rgb(58, 122, 237),
+                inactive_alpha: 0.9,
+                highlight_color: Some(Color32::from_rgb(246, 242, 50)),
+                ..default
+            },
+            Self::Unreal => GizmoVisuals {
+                x_color: Color32::from_rgb(226, 46, 46),
+                y_color: Color32::from_rgb(103, 170, 0),
+                z_color: Color32::from_rgb(44, 126, 239),
+                inactive_alpha: 0.9,
+                highlight_color: Some(Color32::from_rgb(255, 255, 0)),
+                ..default
+            },
+            Self::HighContrast => GizmoVisuals {
+                x_color: Color32::from_rgb(255, 0, 0),
+                y_color: Color32::from_rgb(0, 255, 0),
+                z_color: Color32::from_rgb(0, 128, 255),
+                inactive_alpha: 1.0,
+                highlight_color: Some(Color32::from_rgb(255, 255, 255)),
+                active_color: Some(Color32::from_rgb(255, 255, 0)),
+                disabled_color: Color32::from_rgb(128, 128, 128),
+                stroke_width: 5.0,
+                axis_labels: true,
+                ..default
+            },
+            Self::ColorBlindSafe => GizmoVisuals {
+                x_color: Color32::from_rgb(213, 94, 0),
+                y_color: Color32::from_rgb(240, 228, 66),
+                z_color: Color32::from_rgb(0, 114, 178),
+                highlight_color: Some(Color32::from_rgb(255, 255, 255)),
+                active_color: Some(Color32::from_rgb(204, 121, 167)),
+                axis_labels: true,
+                ..default
+            },
         }
     }
 }
+
+impl From<GizmoTheme> for GizmoVisuals {
+    fn from(theme: GizmoTheme) -> Self {
+        theme.visuals()
+    }
+}
@@ -1,22 +1,28 @@
 use ecolor::Rgba;
 use emath::Pos2;
 use enumset::EnumSet;
+use std::fmt;
 use std::ops::{Add, AddAssign, Sub};
+use std::sync::Arc;
 
 use crate::config::{
-    GizmoConfig, GizmoDirection, GizmoMode, PreparedGizmoConfig, TransformPivotPoint,
+    GizmoConfig, GizmoDirection, GizmoHandleState, GizmoMode, PreparedGizmoConfig, TransformLimits,
+    TransformPivotPoint,
 };
-use crate::math::{screen_to_world, Transform};
+use crate::math::{decompose_affine, evaluate_expression, Transform};
+use crate::placement::SurfaceRaycast;
+use crate::snapping::SnapTargetProvider;
 use crate::GizmoOrientation;
 use epaint::Mesh;
-use glam::{DMat4, DQuat, DVec3};
+use glam::{DAffine3, DMat3, DMat4, DQuat, DVec3};
 
+use crate::subgizmo::bounds::{draw_bounds, BoundsParams};
 use crate::subgizmo::rotation::RotationParams;
 use crate::subgizmo::scale::ScaleParams;
 use crate::subgizmo::translation::TranslationParams;
 use crate::subgizmo::{
-    common::TransformKind, ArcballSubGizmo, RotationSubGizmo, ScaleSubGizmo, SubGizmo,
-    SubGizmoControl, TranslationSubGizmo,
+    common::TransformKind, ArcballSubGizmo, BoundsSubGizmo, RotationSubGizmo, ScaleSubGizmo,
+    SubGizmo, SubGizmoControl, SurfaceSubGizmo, TranslationSubGizmo,
 };
 
 /// A 3D transformation gizmo.
@@ -32,8 +38,32 @@ pub struct Gizmo {
     active_subgizmo_id: Option<u64>,
 
     target_start_transforms: Vec<Transform>,
+    /// Matrices of affine targets at the start of the interaction.
+    target_start_affines: Vec<DAffine3>,
+    /// Range limits of the targets, matched to the targets by index.
+    target_limits: Vec<TransformLimits>,
 
     gizmo_start_transform: Transform,
+    pivot_start_offset: DVec3,
+
+    /// Value typed by the user during the active interaction.
+    typed_input: String,
+    /// Whether Enter was typed, which finishes the active interaction on the next update.
+    typed_enter: bool,
+
+    /// Phase of the drag interaction during the latest update.
+    drag_phase: Option<GizmoDragPhase>,
+    /// Latest result of the active interaction, shown in the value readout.
+    active_result: Option<GizmoResult>,
+    /// Cursor position during the latest update.
+    cursor_pos: Pos2,
+    /// Whether an active interaction was ended by a configuration change.
+    drag_interrupted: bool,
+
+    /// Provider of points that translations snap to.
+    snap_target_provider: Option<Arc<dyn SnapTargetProvider>>,
+    /// Raycast used for placing targets on surfaces.
+    surface_raycast: Option<Arc<dyn SurfaceRaycast>>,
 }
 
 impl Gizmo {
@@ -52,8 +82,12 @@ impl Gizmo {
     /// Updates the configuration used by the gizmo.
     pub fn update_config(&mut self, config: GizmoConfig) {
         if config.modes_changed(&self.config) {
+            // Changing the modes ends the interaction, which is reported on the next update.
+            self.drag_interrupted |= self.active_subgizmo_id.is_some();
             self.subgizmos.clear();
             self.active_subgizmo_id = None;
+            self.typed_input.clear();
+            self.typed_enter = false;
         }
 
         self.config.update_for_config(config);
@@ -62,6 +96,7 @@ impl Gizmo {
             self.add_rotation();
             self.add_translation();
             self.add_scale();
+            self.add_bounds();
         }
     }
 
@@ -70,6 +105,143 @@ impl Gizmo {
         self.subgizmos.iter().any(|subgizmo| subgizmo.is_focused())
     }
 
+    /// Offset of the pivot point from the pivot derived from the targets,
+    /// in the local space (without scale) of the last target.
+    ///
+    /// The offset is edited by dragging the gizmo when [`GizmoConfig::edit_pivot`] is set.
+    /// It is not used with [`TransformPivotPoint::IndividualOrigins`].
+    pub fn pivot_offset(&self) -> mint::Vector3<f64> {
+        self.config.pivot_offset.into()
+    }
+
+    /// Sets the offset of the pivot point. See [`Gizmo::pivot_offset`].
+    pub fn set_pivot_offset(&mut self, offset: mint::Vector3<f64>) {
+        self.config.pivot_offset = offset.into();
+    }
+
+    /// Range limits of the targets. See [`Gizmo::set_target_limits`].
+    pub fn target_limits(&self) -> &[TransformLimits] {
+        &self.target_limits
+    }
+
+    /// Sets range limits for the targets given to [`Gizmo::update`].
+    ///
+    /// The limits are matched to the targets by index. Targets without
+    /// corresponding limits are not limited.
+    pub fn set_target_limits(&mut self, limits: &[TransformLimits]) {
+        self.target_limits = limits.to_vec();
+    }
+
+    /// Phase of the drag interaction during the latest [`Gizmo::update`] call,
+    /// or [`GizmoDragPhase::Cancelled`] after [`Gizmo::cancel`].
+    ///
+    /// [`None`] if the gizmo was not interacted with.
+    pub fn drag_phase(&self) -> Option<GizmoDragPhase> {
+        self.drag_phase
+    }
+
+    /// Latest result of the active interaction, or of the most recent one
+    /// if no interaction is active.
+    pub fn last_result(&self) -> Option<GizmoResult> {
+        self.active_result
+    }
+
+    /// Cancels the active interaction.
+    ///
+    /// Returns the transforms the targets had when the interaction started,
+    /// so that they can be restored. [`None`] is returned if no interaction was active.
+    /// The transforms are in the same space as the targets given to the update call,
+    /// so after [`Gizmo::update_with_parents`] they are in the local space of the parents.
+    ///
+    /// If [`GizmoConfig::mode_override`] is set, a new interaction is started
+    /// on the next update, unless the override is cleared.
+    ///
+    /// Use [`Gizmo::cancel_affine`] for interactions of [`Gizmo::update_affine`].
+    pub fn cancel(&mut self) -> Option<Vec<Transform>> {
+        self.cancel_interaction()?;
+        Some(std::mem::take(&mut self.target_start_transforms))
+    }
+
+    /// Cancels the active interaction of [`Gizmo::update_affine`], like [`Gizmo::cancel`].
+    ///
+    /// Returns the matrices the targets had when the interaction started,
+    /// including their shear. [`None`] is returned if no interaction was active.
+    pub fn cancel_affine(&mut self) -> Option<Vec<mint::ColumnMatrix4<f64>>> {
+        self.cancel_interaction()?;
+        Some(
+            std::mem::take(&mut self.target_start_affines)
+                .into_iter()
+                .map(|affine| DMat4::from(affine).into())
+                .collect(),
+        )
+    }
+
+    /// Ends the active interaction and restores the gizmo to its start.
+    /// Returns [`None`] if no interaction was active.
+    fn cancel_interaction(&mut self) -> Option<()> {
+        let subgizmo = self.active_subgizmo_mut()?;
+        subgizmo.set_active(false);
+        subgizmo.set_focused(false);
+        subgizmo.set_typed_value(None);
+
+        self.active_subgizmo_id = None;
+        self.typed_input.clear();
+        self.typed_enter = false;
+        self.drag_phase = Some(GizmoDragPhase::Cancelled);
+
+        self.config.pivot_offset = self.pivot_start_offset;
+        self.config.update_transform(self.gizmo_start_transform);
+        for subgizmo in &mut self.subgizmos {
+            subgizmo.update_config(self.config);
+        }
+
+        Some(())
+    }
+
+    /// Types a key into the value input of the active subgizmo.
+    ///
+    /// While a subgizmo is active, a typed value overrides the value derived from
+    /// the pointer: distance for translation, angle in degrees for rotation
+    /// and factor for scale. Signs and simple arithmetic, such as `-2.5*2`, are supported.
+    ///
+    /// The interaction is finished with the typed value when the pointer is released,
+    /// or on the next update after [`GizmoKey::Enter`].
+    /// Keys are ignored when no subgizmo is active.
+    pub fn type_key(&mut self, key: GizmoKey) {
+        if self.active_subgizmo_id.is_none() {
+            return;
+        }
+
+        match key {
+            GizmoKey::Char(c) if c.is_ascii_digit() || "+-*/.()".contains(c) => {
+                self.typed_input.push(c);
+            }
+            GizmoKey::Char(_) => {}
+            GizmoKey::Backspace => {
+                self.typed_input.pop();
+            }
+            GizmoKey::Enter => {
+                self.typed_enter = true;
+            }
+        }
+    }
+
+    /// Sets the provider of points that translations snap to when snapping is enabled,
+    /// such as vertices or the origins of other objects.
+    pub fn set_snap_target_provider(&mut self, provider: Option<Arc<dyn SnapTargetProvider>>) {
+        self.snap_target_provider = provider;
+    }
+
+    /// Sets the raycast used by [`GizmoMode::TranslateSurface`] for placing targets on surfaces.
+    pub fn set_surface_raycast(&mut self, raycast: Option<Arc<dyn SurfaceRaycast>>) {
+        self.surface_raycast = raycast;
+    }
+
+    /// Text typed into the value input of the active subgizmo, if any.
+    pub fn typed_input(&self) -> Option<&str> {
+        (!self.typed_input.is_empty()).then_some(self.typed_input.as_str())
+    }
+
     /// Updates the gizmo based on given interaction information.
     ///
     /// # Examples
@@ -103,16 +275,247 @@ impl Gizmo {
     ///
     /// Returns the result of the interaction with the updated transformation.
     ///
-    /// [`Some`] is returned when any of the subgizmos is being dragged, [`None`] otherwise.
+    /// [`Some`] is returned when any of the subgizmos is being dragged, or when
+    /// the interaction is finished with a typed value. [`None`] is returned otherwise.
+    ///
+    /// Use [`Gizmo::drag_phase`] to find out whether the interaction was started,
+    /// updated or committed during this call.
     pub fn update(
         &mut self,
         interaction: GizmoInteraction,
         targets: &[Transform],
     ) -> Option<(GizmoResult, Vec<Transform>)> {
+        let result = self.update_interaction(interaction, targets)?;
+
+        if self.config.edit_pivot {
+            // Only the pivot is moved, the targets stay in place.
+            return Some((result, targets.to_vec()));
+        }
+
+        let updated_targets = self.update_transforms_with_result(
+            result,
+            targets,
+            &self.target_start_transforms,
+            &self.target_limits,
+        );
+
+        if let Some(active_target) = updated_targets.last() {
+            self.config.set_active_target(*active_target);
+        }
+
+        if !self.target_limits.is_empty() {
+            // Keep the gizmo at the targets, which may have been clamped to their limits.
+            self.config.update_for_targets(&updated_targets);
+        }
+
+        Some((result, updated_targets))
+    }
+
+    /// Updates the gizmo based on given interaction information, like [`Gizmo::update`],
+    /// for targets given as affine transformation matrices.
+    ///
+    /// The results are applied to the matrices exactly, so shear and non-uniform
+    /// scale of rotated targets are preserved. The gizmo itself is placed and
+    /// oriented using the scale, rotation and translation decomposed from the matrices.
+    /// For sheared matrices, the local X axis of the gizmo follows the X axis of the
+    /// matrix, and the local Y axis lies in the XY plane of the matrix.
+    ///
+    /// Limits set with [`Gizmo::set_target_limits`] apply to the scale, rotation and
+    /// translation decomposed from the matrices, and keep their shear.
+    /// Use [`Gizmo::cancel_affine`] to restore the matrices from the start of the interaction.
+    pub fn update_affine(
+        &mut self,
+        interaction: GizmoInteraction,
+        targets: &[mint::ColumnMatrix4<f64>],
+    ) -> Option<(GizmoResult, Vec<mint::ColumnMatrix4<f64>>)> {
+        let affines = targets
+            .iter()
+            .map(|target| DAffine3::from_mat4(DMat4::from(*target)))
+            .collect::<Vec<_>>();
+
+        let (result, mut updated_targets) = self.update_affines(interaction, &affines)?;
+
+        if !self.target_limits.is_empty() && !self.config.edit_pivot {
+            // Keep the targets within their limits
+            for (affine, limits) in updated_targets.iter_mut().zip(&self.target_limits) {
+                *affine = limits.clamp_affine(*affine);
+            }
+
+            // Keep the gizmo at the targets, which may have been clamped to their limits.
+            let transforms = updated_targets
+                .iter()
+                .map(|affine| {
+                    let (scale, rotation, translation) = decompose_affine(*affine);
+                    Transform::from_scale_rotation_translation(scale, rotation, translation)
+                })
+                .collect::<Vec<_>>();
+            self.config.update_for_targets(&transforms);
+        }
+
+        let updated_targets = updated_targets
+            .into_iter()
+            .map(|affine| DMat4::from(affine).into())
+            .collect();
+
+        Some((result, updated_targets))
+    }
+
+    /// Updates the gizmo based on given interaction information, like [`Gizmo::update`],
+    /// for targets that are children of other objects.
+    ///
+    /// The targets are given in the local space of their parents, and `parents` contains
+    /// the world space transformation matrix of each target's parent, matched by index.
+    /// Targets without a corresponding parent matrix are in world space.
+    ///
+    /// The gizmo is drawn and dragged in world space, and the updated targets are
+    /// returned in the local space of their parents. Limits set with
+    /// [`Gizmo::set_target_limits`] apply to the local transforms, and
+    /// [`Gizmo::cancel`] returns the local transforms from the start of the interaction.
+    pub fn update_with_parents(
+        &mut self,
+        interaction: GizmoInteraction,
+        targets: &[Transform],
+        parents: &[mint::ColumnMatrix4<f64>],
+    ) -> Option<(GizmoResult, Vec<Transform>)> {
+        let parents = (0..targets.len())
+            .map(|i| {
+                parents.get(i).map_or(DAffine3::IDENTITY, |parent| {
+                    DAffine3::from_mat4(DMat4::from(*parent))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let world_targets = targets
+            .iter()
+            .zip(&parents)
+            .map(|(target, parent)| {
+                *parent
+                    * DAffine3::from_scale_rotation_translation(
+                        target.scale.into(),
+                        target.rotation.into(),
+                        target.translation.into(),
+                    )
+            })
+            .collect::<Vec<_>>();
+
+        let result = self.update_affines(interaction, &world_targets);
+
+        if self.drag_phase == Some(GizmoDragPhase::Started) {
+            // Cancelling restores the targets in the space they were given in
+            self.target_start_transforms = targets.to_vec();
+        }
+
+        let (result, updated_targets) = result?;
+
+        if self.config.edit_pivot {
+            // Only the pivot is moved, the targets stay in place.
+            return Some((result, targets.to_vec()));
+        }
+
+        let local_targets = updated_targets
+            .iter()
+            .zip(&parents)
+            .enumerate()
+            .map(|(i, (world, parent))| {
+                let (scale, rotation, translation) = decompose_affine(parent.inverse() * *world);
+                let transform =
+                    Transform::from_scale_rotation_translation(scale, rotation, translation);
+
+                // Keep the target within its limits
+                self.target_limits
+                    .get(i)
+                    .map_or(transform, |limits| limits.clamp(transform))
+            })
+            .collect::<Vec<_>>();
+
+        if !self.target_limits.is_empty() {
+            // Keep the gizmo at the targets, which may have been clamped to their limits.
+            let world_targets = local_targets
+                .iter()
+                .zip(&parents)
+                .map(|(target, parent)| {
+                    let (scale, rotation, translation) = decompose_affine(
+                        *parent
+                            * DAffine3::from_scale_rotation_translation(
+                                target.scale.into(),
+                                target.rotation.into(),
+                                target.translation.into(),
+                            ),
+                    );
+                    Transform::from_scale_rotation_translation(scale, rotation, translation)
+                })
+                .collect::<Vec<_>>();
+
+            self.config.update_for_targets(&world_targets);
+        }
+
+        Some((result, local_targets))
+    }
+
+    /// Updates the interaction for targets given as affine matrices.
+    /// Returns the result of the interaction with the updated matrices.
+    fn update_affines(
+        &mut self,
+        interaction: GizmoInteraction,
+        affines: &[DAffine3],
+    ) -> Option<(GizmoResult, Vec<DAffine3>)> {
+        let transforms = affines
+            .iter()
+            .map(|affine| {
+                let (scale, rotation, translation) = decompose_affine(*affine);
+                Transform::from_scale_rotation_translation(scale, rotation, translation)
+            })
+            .collect::<Vec<_>>();
+
+        let result = self.update_interaction(interaction, &transforms);
+
+        if self.drag_phase == Some(GizmoDragPhase::Started) {
+            self.target_start_affines = affines.to_vec();
+        }
+
+        let result = result?;
+
+        if self.config.edit_pivot {
+            // Only the pivot is moved, the targets stay in place.
+            return Some((result, affines.to_vec()));
+        }
+
+        let updated_affines =
+            self.update_affines_with_result(result, affines, &self.target_start_affines);
+
+        if let Some(active_affine) = updated_affines.last() {
+            let (scale, rotation, translation) = decompose_affine(*active_affine);
+            self.config
+                .set_active_target(Transform::from_scale_rotation_translation(
+                    scale,
+                    rotation,
+                    translation,
+                ));
+        }
+
+        Some((result, updated_affines))
+    }
+
+    /// Updates the interaction with the gizmo and the gizmo configuration.
+    /// Returns the result of the interaction, if any.
+    fn update_interaction(
+        &mut self,
+        interaction: GizmoInteraction,
+        targets: &[Transform],
+    ) -> Option<GizmoResult> {
+        self.drag_phase = None;
+
         if !self.config.viewport.is_finite() {
             return None;
         }
 
+        // If the interaction was ended by a configuration change, report it
+        // as committed. A new interaction may start on the next update.
+        let interrupted = std::mem::take(&mut self.drag_interrupted);
+        if interrupted {
+            self.drag_phase = Some(GizmoDragPhase::Committed);
+        }
+
         // Update the gizmo based on the given target transforms,
         // unless the gizmo is currently being interacted with.
         if self.active_subgizmo_id.is_none() {
@@ -129,10 +532,11 @@ impl Gizmo {
         let force_active = self.config.mode_override.is_some();
 
         let pointer_ray = self.pointer_ray(Pos2::from(interaction.cursor_pos));
+        self.cursor_pos = pointer_ray.screen_pos;
 
         // If there is no active subgizmo, find which one of them
         // is under the mouse pointer, if any.
-        if self.active_subgizmo_id.is_none() && interaction.hovered {
+        if self.active_subgizmo_id.is_none() && interaction.hovered && !interrupted {
             if let Some(subgizmo) = self.pick_subgizmo(pointer_ray) {
                 subgizmo.set_focused(true);
 
@@ -141,21 +545,48 @@ impl Gizmo {
                     self.active_subgizmo_id = Some(subgizmo.id());
                     self.target_start_transforms = targets.to_vec();
                     self.gizmo_start_transform = self.config.as_transform();
+                    self.pivot_start_offset = self.config.pivot_offset;
+                    self.active_result = None;
+                    self.typed_enter = false;
+                    self.drag_phase = Some(GizmoDragPhase::Started);
                 }
             }
         }
 
         let mut result = None;
 
+        let typed_value = evaluate_expression(&self.typed_input);
+        let snap_target_provider = self.snap_target_provider.clone();
+        let surface_raycast = self.surface_raycast.clone();
+
+        let finished = self.typed_enter || !(interaction.dragging || force_active);
+
         if let Some(subgizmo) = self.active_subgizmo_mut() {
-            if interaction.dragging || force_active {
+            // The typed value is applied once more when the interaction finishes,
+            // so that it is not lost when the pointer is released.
+            if !finished || typed_value.is_some() {
                 subgizmo.set_active(true);
                 subgizmo.set_focused(true);
+                subgizmo.set_typed_value(typed_value);
+                subgizmo.set_snap_target_provider(snap_target_provider);
+                subgizmo.set_surface_raycast(surface_raycast);
                 result = subgizmo.update(pointer_ray);
-            } else {
+            }
+
+            if finished {
                 subgizmo.set_active(false);
                 subgizmo.set_focused(false);
+                subgizmo.set_typed_value(None);
                 self.active_subgizmo_id = None;
+                self.typed_input.clear();
+                self.typed_enter = false;
+                self.drag_phase = Some(GizmoDragPhase::Committed);
+            } else {
+                self.drag_phase.get_or_insert(GizmoDragPhase::Updated);
+            }
+
+            if result.is_some() {
+                self.active_result = result;
             }
         }
 
@@ -173,10 +604,14 @@ impl Gizmo {
 
         self.update_config_with_result(result);
 
-        let updated_targets =
-            self.update_transforms_with_result(result, targets, &self.target_start_transforms);
+        if self.config.edit_pivot {
+            let pivot_delta =
+                self.config.translation - DVec3::from(self.gizmo_start_transform.translation);
+            self.config.pivot_offset =
+                self.pivot_start_offset + self.config.active_rotation.inverse() * pivot_delta;
+        }
 
-        Some((result, updated_targets))
+        Some(result)
     }
 
     /// Return all the necessary data to draw the latest gizmo interaction.
@@ -188,12 +623,40 @@ impl Gizmo {
         }
 
         let mut draw_data = GizmoDrawData::default();
+
+        // The box outline is shown together with its handles
+        if self.subgizmos.iter().any(|subgizmo| {
+            matches!(subgizmo, SubGizmo::Bounds(_))
+                && (self.active_subgizmo_id.is_none() || subgizmo.is_active())
+        }) {
+            draw_data += draw_bounds(&self.config);
+        }
+
         for subgizmo in &self.subgizmos {
             if self.active_subgizmo_id.is_none() || subgizmo.is_active() {
                 draw_data += subgizmo.draw();
             }
         }
 
+        if let Some(result) = self
+            .active_result
+            .filter(|_| self.config.visuals.value_readout && self.active_subgizmo_id.is_some())
+        {
+            let visuals = &self.config.visuals;
+            let color = visuals.state_color(visuals.s_color, GizmoHandleState::Active);
+
+            // The readout is shown above the cursor, so that it is not covered by it
+            draw_data.texts.push(GizmoText {
+                text: result.to_string(),
+                position: [
+                    self.cursor_pos.x,
+                    self.cursor_pos.y - visuals.label_size * 1.5,
+                ],
+                color: Rgba::from(color).to_array(),
+                size: visuals.label_size,
+            });
+        }
+
         draw_data
     }
 
@@ -210,30 +673,199 @@ impl Gizmo {
         result: GizmoResult,
         transforms: &[Transform],
         start_transforms: &[Transform],
+        limits: &[TransformLimits],
     ) -> Vec<Transform> {
         transforms
             .iter()
             .zip(start_transforms)
-            .map(|(transform, start_transform)| match result {
+            .enumerate()
+            .map(|(i, (transform, start_transform))| {
+                let transform = match result {
+                    GizmoResult::Rotation {
+                        axis,
+                        delta,
+                        total: _,
+                        is_view_axis,
+                        euler_channel: _,
+                    } => self.update_rotation(transform, axis, delta, is_view_axis),
+                    GizmoResult::Translation { delta, total: _ } => {
+                        self.update_translation(delta, transform, start_transform)
+                    }
+                    GizmoResult::Scale { total } => {
+                        self.update_scale(transform, start_transform, total)
+                    }
+                    GizmoResult::Arcball { delta, total: _ } => {
+                        self.update_rotation_quat(transform, delta.into())
+                    }
+                    GizmoResult::Surface {
+                        delta: _,
+                        total,
+                        normal,
+                    } => self.update_surface_placement(start_transform, total, normal),
+                    GizmoResult::BoundsScale { total, anchor } => {
+                        self.update_bounds_scale(transform, start_transform, total, anchor)
+                    }
+                };
+
+                // Keep the target within its limits
+                limits
+                    .get(i)
+                    .map_or(transform, |limits| limits.clamp(transform))
+            })
+            .collect()
+    }
+
+    fn update_affines_with_result(
+        &self,
+        result: GizmoResult,
+        affines: &[DAffine3],
+        start_affines: &[DAffine3],
+    ) -> Vec<DAffine3> {
+        affines
+            .iter()
+            .zip(start_affines)
+            .map(|(affine, start_affine)| match result {
                 GizmoResult::Rotation {
                     axis,
                     delta,
                     total: _,
                     is_view_axis,
-                } => self.update_rotation(transform, axis, delta, is_view_axis),
+                    euler_channel: _,
+                } => {
+                    let rotation = decompose_affine(*affine).1;
+                    let delta = self.rotation_delta(rotation, axis, delta, is_view_axis);
+                    self.update_affine_rotation(affine, delta)
+                }
+                GizmoResult::Arcball { delta, total: _ } => {
+                    self.update_affine_rotation(affine, delta.into())
+                }
                 GizmoResult::Translation { delta, total: _ } => {
-                    self.update_translation(delta, transform, start_transform)
+                    let start_rotation = decompose_affine(*start_affine).1;
+                    DAffine3 {
+                        matrix3: start_affine.matrix3,
+                        translation: affine.translation
+                            + self.translation_delta(delta, start_rotation),
+                    }
                 }
                 GizmoResult::Scale { total } => {
-                    self.update_scale(transform, start_transform, total)
+                    self.update_affine_scale(affine, start_affine, total.into())
                 }
-                GizmoResult::Arcball { delta, total: _ } => {
-                    self.update_rotation_quat(transform, delta.into())
+                GizmoResult::Surface {
+                    delta: _,
+                    total,
+                    normal,
+                } => {
+                    self.update_affine_surface_placement(start_affine, total.into(), normal.into())
+                }
+                GizmoResult::BoundsScale { total, anchor } => {
+                    let scale = DVec3::from(total);
+                    let anchor = DVec3::from(anchor);
+                    let axes_rotation = self.config.active_rotation;
+
+                    // Scale the target's offset from the anchor, so that the opposite side stays in place
+                    let offset = start_affine.translation - anchor;
+                    let offset = axes_rotation * (scale * (axes_rotation.inverse() * offset));
+
+                    // Scale along the box axes, which may differ from the target's axes
+                    let axes = DMat3::from_quat(axes_rotation);
+                    DAffine3 {
+                        matrix3: axes
+                            * DMat3::from_diagonal(scale)
+                            * axes.transpose()
+                            * start_affine.matrix3,
+                        translation: anchor + offset,
+                    }
                 }
             })
             .collect()
     }
 
+    fn update_affine_rotation(&self, affine: &DAffine3, delta: DQuat) -> DAffine3 {
+        let pivot = match self.config.pivot_point {
+            TransformPivotPoint::MedianPoint
+            | TransformPivotPoint::BoundingBoxCenter
+            | TransformPivotPoint::ActiveElement
+            | TransformPivotPoint::Point => self.config.translation,
+            TransformPivotPoint::IndividualOrigins => affine.translation,
+        };
+
+        DAffine3::from_translation(pivot)
+            * DAffine3::from_quat(delta)
+            * DAffine3::from_translation(-pivot)
+            * *affine
+    }
+
+    fn update_affine_scale(
+        &self,
+        affine: &DAffine3,
+        start_affine: &DAffine3,
+        scale: DVec3,
+    ) -> DAffine3 {
+        let start_rotation = decompose_affine(*start_affine).1;
+        let axes_rotation = self.scale_axes_rotation(start_rotation);
+
+        let matrix3 = match self.config.orientation() {
+            // Scale along the target's own axes
+            GizmoOrientation::Local | GizmoOrientation::Gimbal(_) => {
+                start_affine.matrix3 * DMat3::from_diagonal(scale)
+            }
+            // Scale along the gizmo axes, which may shear the target
+            _ => {
+                let axes_rotation = DMat3::from_quat(axes_rotation);
+                axes_rotation
+                    * DMat3::from_diagonal(scale)
+                    * axes_rotation.transpose()
+                    * start_affine.matrix3
+            }
+        };
+
+        // Scale the target's offset from the pivot, which is where the gizmo is located
+        let translation = match self.config.pivot_point {
+            TransformPivotPoint::MedianPoint
+            | TransformPivotPoint::BoundingBoxCenter
+            | TransformPivotPoint::ActiveElement
+            | TransformPivotPoint::Point => {
+                let offset = start_affine.translation - self.config.translation;
+                self.config.translation
+                    + axes_rotation * (scale * (axes_rotation.inverse() * offset))
+            }
+            TransformPivotPoint::IndividualOrigins => affine.translation,
+        };
+
+        DAffine3 {
+            matrix3,
+            translation,
+        }
+    }
+
+    fn update_affine_surface_placement(
+        &self,
+        start_affine: &DAffine3,
+        total: DVec3,
+        normal: DVec3,
+    ) -> DAffine3 {
+        let local_axis = match self.config.surface_align_axis {
+            Some(GizmoDirection::X) => Some(DVec3::X),
+            Some(GizmoDirection::Y) => Some(DVec3::Y),
+            Some(GizmoDirection::Z) => Some(DVec3::Z),
+            Some(GizmoDirection::View) | None => None,
+        };
+
+        // Rotate the chosen axis of the target to the surface normal with the shortest arc
+        let matrix3 = match local_axis {
+            Some(local_axis) if normal != DVec3::ZERO => {
+                let axis = (start_affine.matrix3 * local_axis).normalize_or_zero();
+                DMat3::from_quat(DQuat::from_rotation_arc(axis, normal)) * start_affine.matrix3
+            }
+            _ => start_affine.matrix3,
+        };
+
+        DAffine3 {
+            matrix3,
+            translation: start_affine.translation + total,
+        }
+    }
+
     fn update_rotation(
         &self,
         transform: &Transform,
@@ -241,21 +873,42 @@ impl Gizmo {
         delta: f64,
         is_view_axis: bool,
     ) -> Transform {
+        let delta = self.rotation_delta(DQuat::from(transform.rotation), axis, delta, is_view_axis);
+
+        self.update_rotation_quat(transform, delta)
+    }
+
+    /// World space rotation of a target with given rotation, around the given gizmo axis
+    fn rotation_delta(
+        &self,
+        rotation: DQuat,
+        axis: mint::Vector3<f64>,
+        delta: f64,
+        is_view_axis: bool,
+    ) -> DQuat {
         let axis = match self.config.orientation() {
-            GizmoOrientation::Local if !is_view_axis => {
-                (DQuat::from(transform.rotation) * DVec3::from(axis)).normalize()
+            GizmoOrientation::Local if !is_view_axis => (rotation * DVec3::from(axis)).normalize(),
+            GizmoOrientation::Gimbal(order) if !is_view_axis => {
+                (order.gimbal_axes(rotation) * DVec3::from(axis)).normalize()
+            }
+            GizmoOrientation::View | GizmoOrientation::Parent | GizmoOrientation::Custom
+                if !is_view_axis =>
+            {
+                (self.axes_rotation() * DVec3::from(axis)).normalize()
             }
             _ => DVec3::from(axis),
         };
 
-        let delta = DQuat::from_axis_angle(axis, delta);
-
-        self.update_rotation_quat(transform, delta)
+        DQuat::from_axis_angle(axis, delta)
     }
 
     fn update_rotation_quat(&self, transform: &Transform, delta: DQuat) -> Transform {
+        // The gizmo is located at the pivot point, unless individual origins are used
         let translation = match self.config.pivot_point {
-            TransformPivotPoint::MedianPoint => (self.config.translation
+            TransformPivotPoint::MedianPoint
+            | TransformPivotPoint::BoundingBoxCenter
+            | TransformPivotPoint::ActiveElement
+            | TransformPivotPoint::Point => (self.config.translation
                 + delta * (DVec3::from(transform.translation) - self.config.translation))
                 .into(),
             TransformPivotPoint::IndividualOrigins => transform.translation,
@@ -276,10 +929,7 @@ impl Gizmo {
         transform: &Transform,
         start_transform: &Transform,
     ) -> Transform {
-        let delta = match self.config.orientation() {
-            GizmoOrientation::Global => DVec3::from(delta),
-            GizmoOrientation::Local => DQuat::from(start_transform.rotation) * DVec3::from(delta),
-        };
+        let delta = self.translation_delta(delta, DQuat::from(start_transform.rotation));
 
         Transform {
             scale: start_transform.scale,
@@ -288,15 +938,98 @@ impl Gizmo {
         }
     }
 
+    fn update_surface_placement(
+        &self,
+        start_transform: &Transform,
+        total: mint::Vector3<f64>,
+        normal: mint::Vector3<f64>,
+    ) -> Transform {
+        let rotation = DQuat::from(start_transform.rotation);
+        let normal = DVec3::from(normal);
+
+        let local_axis = match self.config.surface_align_axis {
+            Some(GizmoDirection::X) => Some(DVec3::X),
+            Some(GizmoDirection::Y) => Some(DVec3::Y),
+            Some(GizmoDirection::Z) => Some(DVec3::Z),
+            Some(GizmoDirection::View) | None => None,
+        };
+
+        // Rotate the chosen axis of the target to the surface normal with the shortest arc
+        let rotation = match local_axis {
+            Some(local_axis) if normal != DVec3::ZERO => {
+                DQuat::from_rotation_arc(rotation * local_axis, normal) * rotation
+            }
+            _ => rotation,
+        };
+
+        Transform {
+            scale: start_transform.scale,
+            rotation: rotation.into(),
+            translation: (DVec3::from(start_transform.translation) + DVec3::from(total)).into(),
+        }
+    }
+
+    fn update_bounds_scale(
+        &self,
+        transform: &Transform,
+        start_transform: &Transform,
+        scale: mint::Vector3<f64>,
+        anchor: mint::Vector3<f64>,
+    ) -> Transform {
+        let scale = DVec3::from(scale);
+        let anchor = DVec3::from(anchor);
+
+        // The bounding box is aligned to the last target
+        let axes_rotation = self.config.active_rotation;
+
+        // Scale the target's offset from the anchor, so that the opposite side stays in place
+        let offset = DVec3::from(start_transform.translation) - anchor;
+        let offset = axes_rotation * (scale * (axes_rotation.inverse() * offset));
+
+        // Scale along the box axes, and measure the result along the target's own axes.
+        // This is exact for targets whose axes are aligned with the box. Other targets would
+        // need shear, which a `Transform` can not represent, so their scale is approximated.
+        // Use `Gizmo::update_affine` for such targets to keep the shear.
+        let start_scale = DVec3::from(start_transform.scale);
+        let axes = DMat3::from_quat(axes_rotation);
+        let scaled = axes
+            * DMat3::from_diagonal(scale)
+            * axes.transpose()
+            * DMat3::from_quat(DQuat::from(start_transform.rotation))
+            * DMat3::from_diagonal(start_scale);
+        let new_scale = DVec3::new(
+            scaled.x_axis.length(),
+            scaled.y_axis.length(),
+            scaled.z_axis.length(),
+        ) * start_scale.signum();
+
+        Transform {
+            scale: new_scale.into(),
+            translation: (anchor + offset).into(),
+            ..*transform
+        }
+    }
+
     fn update_scale(
         &self,
         transform: &Transform,
         start_transform: &Transform,
         scale: mint::Vector3<f64>,
     ) -> Transform {
+        let scale = DVec3::from(scale);
+
+        let axes_rotation = self.scale_axes_rotation(DQuat::from(start_transform.rotation));
+
         let new_scale = match self.config.orientation() {
-            GizmoOrientation::Global => {
-                let scaled_transform_mat = DMat4::from_scale(scale.into())
+            GizmoOrientation::Local | GizmoOrientation::Gimbal(_) => {
+                DVec3::from(start_transform.scale) * scale
+            }
+            _ => {
+                // Scale along the gizmo axes, which are not aligned to the target's axes
+                let axes_rotation = DMat4::from_quat(axes_rotation);
+                let scaled_transform_mat = axes_rotation
+                    * DMat4::from_scale(scale)
+                    * axes_rotation.inverse()
                     * DMat4::from_scale_rotation_translation(
                         DVec3::from(start_transform.scale),
                         DQuat::from(start_transform.rotation),
@@ -305,20 +1038,64 @@ impl Gizmo {
                 let (scale, _, _) = scaled_transform_mat.to_scale_rotation_translation();
                 scale
             }
-            GizmoOrientation::Local => DVec3::from(start_transform.scale) * DVec3::from(scale),
+        };
+
+        // Scale the target's offset from the pivot, which is where the gizmo is located
+        let translation = match self.config.pivot_point {
+            TransformPivotPoint::MedianPoint
+            | TransformPivotPoint::BoundingBoxCenter
+            | TransformPivotPoint::ActiveElement
+            | TransformPivotPoint::Point => {
+                let offset = DVec3::from(start_transform.translation) - self.config.translation;
+                let offset = axes_rotation * (scale * (axes_rotation.inverse() * offset));
+                (self.config.translation + offset).into()
+            }
+            TransformPivotPoint::IndividualOrigins => transform.translation,
         };
 
         Transform {
             scale: new_scale.into(),
+            translation,
             ..*transform
         }
     }
 
+    /// World space translation of a target with given start rotation, from a translation in gizmo axes
+    fn translation_delta(&self, delta: mint::Vector3<f64>, start_rotation: DQuat) -> DVec3 {
+        match self.config.orientation() {
+            GizmoOrientation::Global => DVec3::from(delta),
+            GizmoOrientation::Local | GizmoOrientation::Gimbal(_) => {
+                start_rotation * DVec3::from(delta)
+            }
+            GizmoOrientation::View | GizmoOrientation::Parent | GizmoOrientation::Custom => {
+                self.axes_rotation() * DVec3::from(delta)
+            }
+        }
+    }
+
+    /// Rotation of the axes along which a target with given start rotation is scaled
+    fn scale_axes_rotation(&self, start_rotation: DQuat) -> DQuat {
+        match self.config.orientation() {
+            GizmoOrientation::Global => DQuat::IDENTITY,
+            GizmoOrientation::Local | GizmoOrientation::Gimbal(_) => start_rotation,
+            GizmoOrientation::View | GizmoOrientation::Parent | GizmoOrientation::Custom => {
+                self.axes_rotation()
+            }
+        }
+    }
+
+    /// Rotation of the gizmo axes at the start of the interaction.
+    /// The axes are shared by all targets in other than local or global orientation.
+    fn axes_rotation(&self) -> DQuat {
+        DQuat::from(self.gizmo_start_transform.rotation)
+    }
+
     fn update_config_with_result(&mut self, result: GizmoResult) {
         let new_config_transform = self.update_transforms_with_result(
             result,
             &[self.config.as_transform()],
             &[self.gizmo_start_transform],
+            &[],
         )[0];
 
         self.config.update_transform(new_config_transform);
@@ -348,9 +1125,16 @@ impl Gizmo {
 
     /// Get all modes that are currently enabled
     fn enabled_modes(&self) -> EnumSet<GizmoMode> {
-        self.config
+        let modes = self
+            .config
             .mode_override
-            .map_or(self.config.modes, EnumSet::only)
+            .map_or(self.config.modes, EnumSet::only);
+
+        if self.config.edit_pivot {
+            modes & GizmoMode::all_translate()
+        } else {
+            modes
+        }
     }
 
     /// Adds rotation subgizmos
@@ -477,7 +1261,7 @@ impl Gizmo {
                     self.config,
                     TranslationParams {
                         mode: GizmoMode::TranslateXY,
-                        direction: GizmoDirection::X,
+                        direction: GizmoDirection::Z,
                         transform_kind: TransformKind::Plane,
                     },
                 )
@@ -505,13 +1289,45 @@ impl Gizmo {
                     self.config,
                     TranslationParams {
                         mode: GizmoMode::TranslateYZ,
-                        direction: GizmoDirection::Z,
+                        direction: GizmoDirection::X,
                         transform_kind: TransformKind::Plane,
                     },
                 )
                 .into(),
             );
         }
+
+        // Surface placement shares the center handle with view plane translation
+        if modes.contains(GizmoMode::TranslateSurface) && !modes.contains(GizmoMode::TranslateView)
+        {
+            self.subgizmos
+                .push(SurfaceSubGizmo::new(self.config, ()).into());
+        }
+    }
+
+    /// Adds bounding box handle subgizmos
+    fn add_bounds(&mut self) {
+        let modes = self.enabled_modes();
+
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let side = [x, y, z];
+                    let mode = match side.iter().filter(|side| **side != 0).count() {
+                        1 => GizmoMode::BoundsFace,
+                        2 => GizmoMode::BoundsEdge,
+                        3 => GizmoMode::BoundsCorner,
+                        _ => continue,
+                    };
+
+                    if modes.contains(mode) {
+                        self.subgizmos.push(
+                            BoundsSubGizmo::new(self.config, BoundsParams { mode, side }).into(),
+                        );
+                    }
+                }
+            }
+        }
     }
 
     /// Adds scale subgizmos
@@ -580,7 +1396,7 @@ impl Gizmo {
                     self.config,
                     ScaleParams {
                         mode: GizmoMode::ScaleXY,
-                        direction: GizmoDirection::X,
+                        direction: GizmoDirection::Z,
                         transform_kind: TransformKind::Plane,
                     },
                 )
@@ -608,7 +1424,7 @@ impl Gizmo {
                     self.config,
                     ScaleParams {
                         mode: GizmoMode::ScaleYZ,
-                        direction: GizmoDirection::Z,
+                        direction: GizmoDirection::X,
                         transform_kind: TransformKind::Plane,
                     },
                 )
@@ -619,11 +1435,16 @@ impl Gizmo {
 
     /// Calculate a world space ray from given screen space position
     fn pointer_ray(&self, screen_pos: Pos2) -> Ray {
-        let mat = self.config.view_projection.inverse();
-        let origin = screen_to_world(self.config.viewport, mat, screen_pos, -1.0);
-        let target = screen_to_world(self.config.viewport, mat, screen_pos, 1.0);
-
-        let direction = target.sub(origin).normalize();
+        let origin = self.config.screen_to_world(screen_pos, -1.0);
+
+        // Rays of orthographic projections are parallel to the view direction
+        let direction = if self.config.orthographic {
+            -self.config.view_backward()
+        } else {
+            // The middle of the depth range stays finite with infinite far planes
+            let target = self.config.screen_to_world(screen_pos, 0.0);
+            target.sub(origin).normalize()
+        };
 
         Ray {
             screen_pos,
@@ -653,8 +1474,40 @@ pub struct GizmoInteraction {
     pub dragging: bool,
 }
 
+/// Phase of a drag interaction with the gizmo.
+///
+/// See [`Gizmo::drag_phase`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GizmoDragPhase {
+    /// A subgizmo became active. The initial result of the interaction
+    /// is returned from the same update.
+    Started,
+    /// The active subgizmo was dragged.
+    Updated,
+    /// The interaction ended and the latest transforms should be kept.
+    Committed,
+    /// The interaction was cancelled with [`Gizmo::cancel`] and the
+    /// transforms from the start of the interaction should be restored.
+    Cancelled,
+}
+
+/// Keystroke used for typing an exact value during a gizmo interaction.
+///
+/// See [`Gizmo::type_key`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GizmoKey {
+    /// A typed character. Only digits, `.`, `(`, `)` and the
+    /// operators `+`, `-`, `*` and `/` are accepted.
+    Char(char),
+    /// Removes the last typed character.
+    Backspace,
+    /// Finishes the active interaction with the typed value.
+    Enter,
+}
+
 /// Result of a gizmo transformation
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GizmoResult {
     Rotation {
         /// The rotation axis,
@@ -665,6 +1518,9 @@ pub enum GizmoResult {
         total: f64,
         /// Whether we are rotating along the view axis
         is_view_axis: bool,
+        /// In [`GizmoOrientation::Gimbal`], the Euler angle that is rotated.
+        /// The angle changes by `delta`.
+        euler_channel: Option<GizmoDirection>,
     },
     Translation {
         /// The latest translation delta
@@ -682,6 +1538,79 @@ pub enum GizmoResult {
         /// Total rotation of the gizmo interaction
         total: mint::Quaternion<f64>,
     },
+    Surface {
+        /// The latest translation delta, in world space
+        delta: mint::Vector3<f64>,
+        /// Total translation of the gizmo interaction, in world space
+        total: mint::Vector3<f64>,
+        /// Normal of the surface the targets were placed on, in world space
+        normal: mint::Vector3<f64>,
+    },
+    BoundsScale {
+        /// Total scale of the gizmo interaction, along the axes of the bounding box
+        total: mint::Vector3<f64>,
+        /// World space point on the opposite side of the bounding box, which stays in place
+        anchor: mint::Vector3<f64>,
+    },
+}
+
+/// Formats the total of the interaction, as shown in the value readout of [`Gizmo::draw`].
+///
+/// Translations are shown as distances, rotations in degrees and scales as factors.
+/// Translations along a single axis are shown as signed distances along the axis.
+///
+/// # Examples
+///
+/// ```
+/// # use transform_gizmo::GizmoResult;
+/// let result = GizmoResult::Scale {
+///     total: [2.0, 2.0, 2.0].into(),
+/// };
+/// assert_eq!(result.to_string(), "×2.00");
+///
+/// let result = GizmoResult::Translation {
+///     delta: [0.0, 0.0, 1.0].into(),
+///     total: [3.0, 0.0, 4.0].into(),
+/// };
+/// assert_eq!(result.to_string(), "5.00");
+///
+/// let result = GizmoResult::Translation {
+///     delta: [-1.0, 0.0, 0.0].into(),
+///     total: [-2.0, 0.0, 0.0].into(),
+/// };
+/// assert_eq!(result.to_string(), "-2.00");
+/// ```
+impl fmt::Display for GizmoResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Rotation { total, .. } => write!(f, "{:.1}°", total.to_degrees()),
+            Self::Arcball { total, .. } => {
+                let (_, angle) = DQuat::from(total).to_axis_angle();
+                write!(f, "{:.1}°", angle.to_degrees())
+            }
+            Self::Translation { total, .. } | Self::Surface { total, .. } => {
+                let total = DVec3::from(total);
+                let length = total.length();
+
+                // Translations along a single axis are shown with their sign
+                let distance = total
+                    .to_array()
+                    .into_iter()
+                    .find(|value| *value != 0.0 && length - value.abs() <= length * 1e-9)
+                    .unwrap_or(length);
+
+                write!(f, "{distance:.2}")
+            }
+            Self::Scale { total } | Self::BoundsScale { total, .. } => {
+                let total = DVec3::from(total);
+                if total.x == total.y && total.y == total.z {
+                    write!(f, "×{:.2}", total.x)
+                } else {
+                    write!(f, "×{:.2}, {:.2}, {:.2}", total.x, total.y, total.z)
+                }
+            }
+        }
+    }
 }
 
 /// Data used to draw [`Gizmo`].
@@ -693,6 +1622,23 @@ pub struct GizmoDrawData {
     pub colors: Vec<[f32; 4]>,
     /// Indices to the vertex data.
     pub indices: Vec<u32>,
+    /// Text to draw on top of the vertex data, such as axis labels.
+    /// Text is not included in the vertex data and should be drawn
+    /// with the text rendering of your framework.
+    pub texts: Vec<GizmoText>,
+}
+
+/// Text to draw, such as an axis label.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct GizmoText {
+    /// The text to draw
+    pub text: String,
+    /// Center of the text in viewport space.
+    pub position: [f32; 2],
+    /// Linear RGBA color, in the same format as [`GizmoDrawData::colors`].
+    pub color: [f32; 4],
+    /// Font size in pixels
+    pub size: f32,
 }
 
 impl From<Mesh> for GizmoDrawData {
@@ -712,6 +1658,7 @@ impl From<Mesh> for GizmoDrawData {
             vertices,
             colors,
             indices: mesh.indices,
+            texts: Vec::new(),
         }
     }
 }
@@ -723,6 +1670,7 @@ impl AddAssign for GizmoDrawData {
         self.colors.extend(rhs.colors);
         self.indices
             .extend(rhs.indices.into_iter().map(|idx| index_offset + idx));
+        self.texts.extend(rhs.texts);
     }
 }
 
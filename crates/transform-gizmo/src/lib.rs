@@ -17,6 +17,12 @@
 //!
 //! For a more complete example, see the online demo at <https://urholaukkarinen.github.io/transform-gizmo/>.
 //! The demo sources can be found at <https://github.com/urholaukkarinen/transform-gizmo/blob/main/examples/bevy/src/main.rs>.
+//!
+//! # Features
+//!
+//! - `serde`: Implements `Serialize` and `Deserialize` for the gizmo configuration, visuals,
+//!   modes, results and [`math::Transform`]. Missing fields of configurations are filled
+//!   in with their defaults.
 
 mod shape;
 mod subgizmo;
@@ -24,6 +30,10 @@ mod subgizmo;
 pub mod config;
 pub mod gizmo;
 pub mod math;
+pub mod navigation;
+pub mod placement;
+pub mod simulation;
+pub mod snapping;
 
 pub mod prelude;
 
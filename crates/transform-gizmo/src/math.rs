@@ -1,7 +1,10 @@
 pub use emath::{Pos2, Rect, Vec2};
 pub use glam::{DMat3, DMat4, DQuat, DVec2, DVec3, DVec4, Mat4, Quat, Vec3, Vec4Swizzles};
 
+use glam::DAffine3;
+
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transform {
     pub scale: mint::Vector3<f64>,
     pub rotation: mint::Quaternion<f64>,
@@ -53,6 +56,38 @@ pub(crate) fn rotation_align(from: DVec3, to: DVec3) -> DMat3 {
     ])
 }
 
+/// Decomposes an affine matrix into scale, rotation and translation.
+///
+/// Unlike [`DAffine3::to_scale_rotation_translation`], this gives a unit rotation for
+/// sheared matrices too. The columns are orthonormalized with Gram-Schmidt, so the
+/// rotated X and Y axes follow the X axis and the XY plane of the matrix. The scale
+/// is the extent of each column along the corresponding rotated axis.
+pub(crate) fn decompose_affine(affine: DAffine3) -> (DVec3, DQuat, DVec3) {
+    let matrix = affine.matrix3;
+
+    // Mirrored matrices are represented with a negative X scale
+    let sign = if matrix.determinant() < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+
+    let x = (matrix.x_axis * sign).try_normalize().unwrap_or(DVec3::X);
+    let y = (matrix.y_axis - x * x.dot(matrix.y_axis))
+        .try_normalize()
+        .unwrap_or_else(|| x.any_orthonormal_vector());
+    let z = x.cross(y);
+
+    let rotation = DQuat::from_mat3(&DMat3::from_cols(x, y, z)).normalize();
+    let scale = DVec3::new(
+        x.dot(matrix.x_axis),
+        y.dot(matrix.y_axis),
+        z.dot(matrix.z_axis),
+    );
+
+    (scale, rotation, affine.translation)
+}
+
 /// Finds points on two rays that are closest to each other.
 /// This can be used to determine the shortest distance between those two rays.
 ///
@@ -221,3 +256,87 @@ pub(crate) fn screen_to_world(viewport: Rect, mat: DMat4, pos: Pos2, z: f64) ->
 
     world_pos.xyz()
 }
+
+/// Evaluates a simple arithmetic expression, such as `-2.5 * 2 + 1`.
+///
+/// Supports `+`, `-`, `*`, `/`, parentheses and signed numbers.
+/// Returns [`None`] if the expression is incomplete or invalid.
+pub(crate) fn evaluate_expression(expression: &str) -> Option<f64> {
+    let mut parser = ExpressionParser {
+        chars: expression.chars().filter(|c| !c.is_whitespace()).collect(),
+        pos: 0,
+    };
+
+    let value = parser.sum()?;
+
+    if parser.pos == parser.chars.len() && value.is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Recursive descent parser used by [`evaluate_expression`]
+struct ExpressionParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl ExpressionParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn sum(&mut self) -> Option<f64> {
+        let mut value = self.product()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.product()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Some(value)
+    }
+
+    fn product(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            value = if op == '*' { value * rhs } else { value / rhs };
+        }
+        Some(value)
+    }
+
+    fn factor(&mut self) -> Option<f64> {
+        match self.peek()? {
+            '-' => {
+                self.pos += 1;
+                self.factor().map(|value| -value)
+            }
+            '+' => {
+                self.pos += 1;
+                self.factor()
+            }
+            '(' => {
+                self.pos += 1;
+                let value = self.sum()?;
+                if self.peek()? != ')' {
+                    return None;
+                }
+                self.pos += 1;
+                Some(value)
+            }
+            _ => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+                    self.pos += 1;
+                }
+                self.chars[start..self.pos]
+                    .iter()
+                    .collect::<String>()
+                    .parse()
+                    .ok()
+            }
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//! A clickable view cube for navigating the camera.
+//!
+//! The view cube is drawn in a corner of the viewport and rotates together with the camera.
+//! Clicking one of its faces, edges or corners gives a view rotation that looks at that
+//! part of the cube, which can be used for snapping the camera to front, top or side views.
+
+use ecolor::Color32;
+use emath::{Pos2, Rect};
+use glam::{DMat4, DQuat, DVec3};
+
+use crate::config::{GizmoConfig, GizmoDirection, GizmoHandleState, PreparedGizmoConfig};
+use crate::gizmo::{GizmoDrawData, GizmoInteraction};
+use crate::math::screen_to_world;
+use crate::shape::ShapeBuidler;
+
+/// Width of the edge and corner regions on each face, relative to the size of the cube.
+const BORDER: f64 = 0.2;
+
+/// A clickable view cube.
+///
+/// # Examples
+///
+/// ```
+/// # use transform_gizmo::GizmoInteraction;
+/// # use transform_gizmo::navigation::ViewCube;
+/// # let cursor_pos = Default::default();
+/// # let clicked = true;
+/// let mut view_cube = ViewCube::default();
+///
+/// view_cube.update_config(Default::default());
+///
+/// let interaction = GizmoInteraction {
+///     cursor_pos,
+///     hovered: true,
+///     drag_started: clicked,
+///     dragging: clicked,
+/// };
+///
+/// if let Some(result) = view_cube.update(interaction) {
+///     // Rotate the camera to `result.view_rotation`
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ViewCube {
+    config: PreparedGizmoConfig,
+    cube_config: ViewCubeConfig,
+    hovered: Option<ViewCubeElement>,
+}
+
+impl ViewCube {
+    /// Creates a new view cube from given configuration
+    pub fn new(cube_config: ViewCubeConfig) -> Self {
+        Self {
+            cube_config,
+            ..Default::default()
+        }
+    }
+
+    /// Current gizmo configuration used by the view cube.
+    pub fn config(&self) -> &GizmoConfig {
+        &self.config
+    }
+
+    /// Current configuration of the view cube.
+    pub fn cube_config(&self) -> &ViewCubeConfig {
+        &self.cube_config
+    }
+
+    /// Updates the configuration of the view cube.
+    pub fn update_cube_config(&mut self, cube_config: ViewCubeConfig) {
+        self.cube_config = cube_config;
+    }
+
+    /// Updates the view, viewport and visuals used by the view cube.
+    /// Only the rotation of the view matrix is used.
+    pub fn update_config(&mut self, config: GizmoConfig) {
+        self.config.update_for_config(config);
+    }
+
+    /// Part of the view cube currently under the cursor, if any.
+    pub fn hovered(&self) -> Option<ViewCubeElement> {
+        self.hovered
+    }
+
+    /// Updates the view cube based on given interaction information.
+    ///
+    /// Returns [`Some`] when a part of the view cube was clicked this frame.
+    pub fn update(&mut self, interaction: GizmoInteraction) -> Option<ViewCubeResult> {
+        let cursor_pos = Pos2::from(interaction.cursor_pos);
+
+        self.hovered = if interaction.hovered && self.rect().contains(cursor_pos) {
+            self.pick(cursor_pos)
+        } else {
+            None
+        };
+
+        if !interaction.drag_started {
+            return None;
+        }
+
+        let element = self.hovered?;
+        let view_forward = -DVec3::from(element.direction());
+
+        let up = if view_forward.y.abs() > 0.999 {
+            DVec3::Z * -view_forward.y.signum()
+        } else {
+            DVec3::Y
+        };
+
+        let view_matrix = if self.config.left_handed {
+            DMat4::look_to_lh(DVec3::ZERO, view_forward, up)
+        } else {
+            DMat4::look_to_rh(DVec3::ZERO, view_forward, up)
+        };
+
+        Some(ViewCubeResult {
+            element,
+            view_rotation: DQuat::from_mat4(&view_matrix).into(),
+            view_forward: view_forward.into(),
+        })
+    }
+
+    /// Return all the necessary data to draw the view cube.
+    ///
+    /// The draw data consists of vertices in viewport coordinates.
+    pub fn draw(&self) -> GizmoDrawData {
+        if !self.config.viewport.is_finite() {
+            return GizmoDrawData::default();
+        }
+
+        let shape_builder = ShapeBuidler::new(
+            self.view_projection(),
+            self.rect(),
+            self.config.pixels_per_point,
+        );
+
+        let mut draw_data = GizmoDrawData::default();
+
+        for (axis, direction) in [GizmoDirection::X, GizmoDirection::Y, GizmoDirection::Z]
+            .into_iter()
+            .enumerate()
+        {
+            for sign in [-1, 1] {
+                let mut normal = DVec3::ZERO;
+                normal[axis] = sign as f64;
+
+                // Only faces towards the camera are drawn
+                if normal.dot(self.look_direction()) >= 0.0 {
+                    continue;
+                }
+
+                let u = (axis + 1) % 3;
+                let v = (axis + 2) % 3;
+                let bounds = [-0.5, -0.5 + BORDER, 0.5 - BORDER, 0.5];
+
+                for i in 0..3 {
+                    for j in 0..3 {
+                        let mut side = [0; 3];
+                        side[axis] = sign;
+                        side[u] = i as i8 - 1;
+                        side[v] = j as i8 - 1;
+                        let element = ViewCubeElement { side };
+
+                        let point = |a: usize, b: usize| {
+                            let mut point = normal * 0.5;
+                            point[u] = bounds[a];
+                            point[v] = bounds[b];
+                            point
+                        };
+
+                        draw_data += shape_builder
+                            .polygon(
+                                &[
+                                    point(i, j),
+                                    point(i + 1, j),
+                                    point(i + 1, j + 1),
+                                    point(i, j + 1),
+                                ],
+                                self.element_color(element, direction),
+                                (0.0, Color32::TRANSPARENT),
+                            )
+                            .into();
+                    }
+                }
+            }
+        }
+
+        draw_data
+    }
+
+    fn element_color(&self, element: ViewCubeElement, direction: GizmoDirection) -> Color32 {
+        let visuals = &self.config.visuals;
+
+        // Faces are colored by their axis, edges and corners are darker
+        let color = match (element.kind(), direction) {
+            (ViewCubeElementKind::Face, GizmoDirection::X) => visuals.x_color,
+            (ViewCubeElementKind::Face, GizmoDirection::Y) => visuals.y_color,
+            (ViewCubeElementKind::Face, GizmoDirection::Z) => visuals.z_color,
+            _ => visuals.s_color.gamma_multiply(0.5),
+        };
+
+        let state = if self.hovered == Some(element) {
+            GizmoHandleState::Hovered
+        } else {
+            GizmoHandleState::Inactive
+        };
+
+        visuals.state_color(color, state)
+    }
+
+    fn pick(&self, cursor_pos: Pos2) -> Option<ViewCubeElement> {
+        let look_direction = self.look_direction();
+        let point = screen_to_world(
+            self.rect(),
+            self.view_projection().inverse(),
+            cursor_pos,
+            0.0,
+        );
+        let origin = point - look_direction * 2.0;
+
+        // Intersect the ray with the cube, which spans from -0.5 to 0.5 on each axis
+        let mut t_enter = f64::MIN;
+        let mut t_exit = f64::MAX;
+        for axis in 0..3 {
+            if look_direction[axis].abs() < 1e-10 {
+                if origin[axis].abs() > 0.5 {
+                    return None;
+                }
+                continue;
+            }
+
+            let t1 = (-0.5 - origin[axis]) / look_direction[axis];
+            let t2 = (0.5 - origin[axis]) / look_direction[axis];
+            t_enter = t_enter.max(t1.min(t2));
+            t_exit = t_exit.min(t1.max(t2));
+        }
+
+        if t_enter > t_exit {
+            return None;
+        }
+
+        let hit = origin + look_direction * t_enter;
+
+        let mut side = [0; 3];
+        for (axis, side) in side.iter_mut().enumerate() {
+            if hit[axis] >= 0.5 - BORDER {
+                *side = 1;
+            } else if hit[axis] <= -0.5 + BORDER {
+                *side = -1;
+            }
+        }
+
+        Some(ViewCubeElement { side })
+    }
+
+    /// Direction the camera is looking at, in world space
+    fn look_direction(&self) -> DVec3 {
+        -self.config.view_backward()
+    }
+
+    /// Orthographic projection of the cube, rotated with the view
+    fn view_projection(&self) -> DMat4 {
+        let view_rotation = DQuat::from_mat4(&DMat4::from(self.config.view_matrix)).normalize();
+
+        let projection = if self.config.left_handed {
+            DMat4::orthographic_lh(-1.0, 1.0, -1.0, 1.0, -2.0, 2.0)
+        } else {
+            DMat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, -2.0, 2.0)
+        };
+
+        projection * DMat4::from_quat(view_rotation)
+    }
+
+    /// Area of the viewport covered by the view cube
+    fn rect(&self) -> Rect {
+        let viewport = self.config.viewport;
+        let size = self.cube_config.size;
+        let margin = self.cube_config.margin;
+
+        Rect::from_min_size(
+            Pos2::new(viewport.max.x - margin - size, viewport.min.y + margin),
+            emath::vec2(size, size),
+        )
+    }
+}
+
+/// Configuration of the view cube.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ViewCubeConfig {
+    /// Size of the view cube area in pixels
+    pub size: f32,
+    /// Distance of the view cube from the top right corner of the viewport, in pixels
+    pub margin: f32,
+}
+
+impl Default for ViewCubeConfig {
+    fn default() -> Self {
+        Self {
+            size: 100.0,
+            margin: 10.0,
+        }
+    }
+}
+
+/// A face, edge or corner of the view cube.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ViewCubeElement {
+    /// Side of the cube along each world axis, either -1, 0 or 1.
+    /// Faces have one non-zero component, edges two and corners three.
+    pub side: [i8; 3],
+}
+
+impl ViewCubeElement {
+    /// Whether this element is a face, edge or corner.
+    pub fn kind(&self) -> ViewCubeElementKind {
+        match self.side.iter().filter(|side| **side != 0).count() {
+            0 | 1 => ViewCubeElementKind::Face,
+            2 => ViewCubeElementKind::Edge,
+            _ => ViewCubeElementKind::Corner,
+        }
+    }
+
+    /// Direction from the center of the cube to this element, in world space.
+    pub fn direction(&self) -> mint::Vector3<f64> {
+        DVec3::new(
+            self.side[0] as f64,
+            self.side[1] as f64,
+            self.side[2] as f64,
+        )
+        .normalize_or_zero()
+        .into()
+    }
+}
+
+/// Kind of a [`ViewCubeElement`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ViewCubeElementKind {
+    Face,
+    Edge,
+    Corner,
+}
+
+/// Result of clicking the view cube.
+#[derive(Debug, Copy, Clone)]
+pub struct ViewCubeResult {
+    /// Part of the cube that was clicked
+    pub element: ViewCubeElement,
+    /// Rotation of a view matrix that looks at the clicked element from outside the cube.
+    /// The view matrix uses the same handedness as the configured projection.
+    /// Views along the world Y axis use world Z as their up direction, other views world Y.
+    pub view_rotation: mint::Quaternion<f64>,
+    /// Direction the camera should look at, in world space
+    pub view_forward: mint::Vector3<f64>,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,97 @@
+use super::*;
+
+/// Center of the view cube in a 800x800 viewport with the default view cube configuration
+const CENTER: (f32, f32) = (740.0, 60.0);
+
+/// Half of the side of the cube in pixels, when it is viewed along an axis
+const HALF_SIDE: f32 = 25.0;
+
+fn assert_vec_eq(actual: impl Into<DVec3>, expected: DVec3) {
+    let actual = actual.into();
+    assert!(
+        actual.abs_diff_eq(expected, 1e-6),
+        "expected {expected}, got {actual}"
+    );
+}
+
+/// View cube of a camera at `eye` looking at the origin
+fn looking_from(eye: DVec3, up: DVec3) -> ViewCube {
+    let mut view_cube = ViewCube::default();
+    view_cube.update_config(GizmoConfig {
+        view_matrix: DMat4::look_at_rh(eye, DVec3::ZERO, up).into(),
+        projection_matrix: DMat4::perspective_rh_gl(1.0, 1.0, 0.1, 100.0).into(),
+        viewport: Rect::from_min_size(Pos2::ZERO, emath::vec2(800.0, 800.0)),
+        ..Default::default()
+    });
+    view_cube
+}
+
+/// Clicks the view cube at given offset from its center
+fn click(view_cube: &mut ViewCube, offset: (f32, f32)) -> Option<ViewCubeResult> {
+    view_cube.update(GizmoInteraction {
+        cursor_pos: (CENTER.0 + offset.0, CENTER.1 + offset.1),
+        hovered: true,
+        drag_started: true,
+        dragging: true,
+    })
+}
+
+#[test]
+fn picks_faces_edges_and_corners() {
+    let mut view_cube = looking_from(DVec3::Z * 5.0, DVec3::Y);
+    let edge = HALF_SIDE * 0.8;
+
+    for (offset, side, kind) in [
+        ((0.0, 0.0), [0, 0, 1], ViewCubeElementKind::Face),
+        ((edge, 0.0), [1, 0, 1], ViewCubeElementKind::Edge),
+        ((0.0, edge), [0, -1, 1], ViewCubeElementKind::Edge),
+        ((-edge, -edge), [-1, 1, 1], ViewCubeElementKind::Corner),
+    ] {
+        let result = click(&mut view_cube, offset).unwrap();
+
+        assert_eq!(result.element.side, side, "offset {offset:?}");
+        assert_eq!(result.element.kind(), kind);
+        assert_eq!(view_cube.hovered(), Some(result.element));
+    }
+}
+
+#[test]
+fn ray_misses_outside_the_cube() {
+    let mut view_cube = looking_from(DVec3::Z * 5.0, DVec3::Y);
+
+    assert!(click(&mut view_cube, (HALF_SIDE + 5.0, 0.0)).is_none());
+    assert!(view_cube.hovered().is_none());
+
+    // The corner of a rotated cube covers the center of the view cube area
+    let mut view_cube = looking_from(DVec3::splat(5.0), DVec3::Y);
+    let result = click(&mut view_cube, (0.0, 0.0)).unwrap();
+    assert_eq!(result.element.side, [1, 1, 1]);
+}
+
+#[test]
+fn views_look_at_the_clicked_element() {
+    let mut view_cube = looking_from(DVec3::Z * 5.0, DVec3::Y);
+    let result = click(&mut view_cube, (0.0, 0.0)).unwrap();
+    let rotation = DQuat::from(result.view_rotation);
+
+    assert_vec_eq(result.view_forward, DVec3::NEG_Z);
+    assert_vec_eq(rotation.inverse() * DVec3::NEG_Z, DVec3::NEG_Z);
+    assert_vec_eq(rotation.inverse() * DVec3::Y, DVec3::Y);
+}
+
+#[test]
+fn top_and_bottom_views_use_z_as_up() {
+    for (eye, up, forward, expected_up) in [
+        (DVec3::Y, DVec3::NEG_Z, DVec3::NEG_Y, DVec3::Z),
+        (DVec3::NEG_Y, DVec3::Z, DVec3::Y, DVec3::NEG_Z),
+    ] {
+        let mut view_cube = looking_from(eye * 5.0, up);
+        let result = click(&mut view_cube, (0.0, 0.0)).unwrap();
+        let rotation = DQuat::from(result.view_rotation);
+
+        assert_vec_eq(result.element.direction(), -forward);
+        assert_vec_eq(result.view_forward, forward);
+        assert_vec_eq(rotation.inverse() * DVec3::NEG_Z, forward);
+        assert_vec_eq(rotation.inverse() * DVec3::Y, expected_up);
+    }
+}
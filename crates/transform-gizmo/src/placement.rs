@@ -0,0 +1,48 @@
+//! Placing targets on scene geometry with [`crate::GizmoMode::TranslateSurface`].
+
+use std::fmt::{Debug, Formatter};
+
+/// Casts rays against the scene geometry of the host application.
+///
+/// While [`crate::GizmoMode::TranslateSurface`] is dragged, the pointer ray is cast
+/// against the scene and the targets are moved to the hit point.
+/// The targets themselves should usually be excluded from the raycast.
+///
+/// Closures of the form `Fn(mint::Vector3<f64>, mint::Vector3<f64>) -> Option<SurfaceHit>`
+/// implement this trait.
+pub trait SurfaceRaycast: Send + Sync {
+    /// Returns the nearest surface hit by the ray, if any.
+    fn raycast(
+        &self,
+        ray_origin: mint::Vector3<f64>,
+        ray_direction: mint::Vector3<f64>,
+    ) -> Option<SurfaceHit>;
+}
+
+impl<F> SurfaceRaycast for F
+where
+    F: Fn(mint::Vector3<f64>, mint::Vector3<f64>) -> Option<SurfaceHit> + Send + Sync,
+{
+    fn raycast(
+        &self,
+        ray_origin: mint::Vector3<f64>,
+        ray_direction: mint::Vector3<f64>,
+    ) -> Option<SurfaceHit> {
+        self(ray_origin, ray_direction)
+    }
+}
+
+impl Debug for dyn SurfaceRaycast {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SurfaceRaycast")
+    }
+}
+
+/// A point on a surface hit by a ray.
+#[derive(Debug, Copy, Clone)]
+pub struct SurfaceHit {
+    /// Hit point in world space
+    pub point: mint::Vector3<f64>,
+    /// Surface normal at the hit point in world space
+    pub normal: mint::Vector3<f64>,
+}
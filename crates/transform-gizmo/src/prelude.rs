@@ -1,5 +1,7 @@
 pub use crate::config::{GizmoConfig, GizmoDirection, GizmoMode, GizmoOrientation, GizmoVisuals};
-pub use crate::gizmo::{Gizmo, GizmoDrawData, GizmoInteraction, GizmoResult};
+pub use crate::gizmo::{
+    Gizmo, GizmoDragPhase, GizmoDrawData, GizmoInteraction, GizmoKey, GizmoResult, GizmoText,
+};
 
 pub use enumset::{enum_set, EnumSet};
 
@@ -0,0 +1,1318 @@
+//! Headless simulation of gizmo interactions, for example for regression tests.
+//!
+//! [`Simulation`] drives a [`Gizmo`] with a scripted sequence of [`SimulationStep`]s
+//! and records the resulting transforms and [`GizmoResult`]s. Besides pointer
+//! interactions, steps cover typed keys, cancelling, parented and affine targets,
+//! and changes to the configuration and the target limits, so no window
+//! or renderer is involved.
+//!
+//! A simulation can be saved as a [`Recording`], which has a plain text format.
+//! The recording stores the whole configuration at the start and every step,
+//! so recordings of bug sessions can be replayed later and checked against the
+//! transforms and results that were recorded.
+//!
+//! # Examples
+//!
+//! ```
+//! use transform_gizmo::math::{DMat4, DVec3, Pos2, Rect, Transform};
+//! use transform_gizmo::simulation::{Recording, Simulation};
+//! use transform_gizmo::{GizmoConfig, GizmoMode};
+//!
+//! let config = GizmoConfig {
+//!     view_matrix: DMat4::look_at_rh(DVec3::Z * 10.0, DVec3::ZERO, DVec3::Y).into(),
+//!     projection_matrix: DMat4::perspective_rh_gl(1.0, 1.0, 0.1, 100.0).into(),
+//!     viewport: Rect::from_min_size(Pos2::ZERO, (800.0, 800.0).into()),
+//!     modes: GizmoMode::TranslateX.into(),
+//!     ..Default::default()
+//! };
+//!
+//! // Drag the X axis arrow to the right
+//! let mut simulation = Simulation::new(config, &[Transform::default()]);
+//! simulation.drag((450.0, 400.0), (500.0, 400.0), 5);
+//! assert!(simulation.targets()[0].translation.x > 0.0);
+//!
+//! // Enable snapping and drag again
+//! simulation.update_config(GizmoConfig {
+//!     snapping: true,
+//!     ..config
+//! });
+//! simulation.drag((500.0, 400.0), (520.0, 400.0), 5);
+//!
+//! let recording: Recording = simulation.recording().to_string().parse().unwrap();
+//! assert_eq!(recording.first_mismatch(1e-9), None);
+//! ```
+
+use std::fmt::{Debug, Display, Formatter};
+use std::str::{FromStr, SplitWhitespace};
+
+use ecolor::Color32;
+use emath::{Pos2, Rect};
+use enumset::{EnumSet, EnumSetType};
+use glam::DAffine3;
+
+use crate::config::{
+    ClipSpace, DepthRange, EulerOrder, GizmoBounds, GizmoOrientation, SnapMode, TransformLimits,
+    TransformPivotPoint,
+};
+use crate::math::{decompose_affine, world_to_screen, DMat4, DQuat, Transform};
+use crate::{
+    Gizmo, GizmoConfig, GizmoDirection, GizmoDragPhase, GizmoInteraction, GizmoKey, GizmoMode,
+    GizmoResult, GizmoVisuals,
+};
+
+/// First line of the recording text format
+const RECORDING_HEADER: &str = "transform-gizmo-recording 2";
+
+/// All Euler orders, for parsing their names
+const EULER_ORDERS: [EulerOrder; 6] = [
+    EulerOrder::XYZ,
+    EulerOrder::XZY,
+    EulerOrder::YXZ,
+    EulerOrder::YZX,
+    EulerOrder::ZXY,
+    EulerOrder::ZYX,
+];
+
+/// A gizmo driven by scripted steps.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    gizmo: Gizmo,
+    initial_config: GizmoConfig,
+    initial_targets: Vec<Transform>,
+    targets: Vec<Transform>,
+    /// Targets as matrices, which keep the shear of affine updates
+    matrices: Vec<DAffine3>,
+    /// Whether the latest interaction was updated with [`SimulationStep::UpdateAffine`]
+    affine_interaction: bool,
+    frames: Vec<SimulationFrame>,
+}
+
+impl Simulation {
+    /// Creates a new simulation of a gizmo with given configuration and targets.
+    pub fn new(config: GizmoConfig, targets: &[Transform]) -> Self {
+        let mut simulation = Self {
+            gizmo: Gizmo::new(config),
+            initial_config: config,
+            initial_targets: targets.to_vec(),
+            targets: Vec::new(),
+            matrices: Vec::new(),
+            affine_interaction: false,
+            frames: Vec::new(),
+        };
+        simulation.set_targets(targets.to_vec());
+        simulation
+    }
+
+    /// The simulated gizmo.
+    ///
+    /// The gizmo can only be changed with steps, see [`SimulationStep`],
+    /// so that every change is recorded.
+    pub fn gizmo(&self) -> &Gizmo {
+        &self.gizmo
+    }
+
+    /// Current transforms of the targets.
+    pub fn targets(&self) -> &[Transform] {
+        &self.targets
+    }
+
+    /// Current targets as affine transformation matrices.
+    ///
+    /// These are given to [`Gizmo::update_affine`] in [`SimulationStep::UpdateAffine`],
+    /// and keep the shear of the returned matrices.
+    pub fn matrices(&self) -> Vec<mint::ColumnMatrix4<f64>> {
+        self.matrices
+            .iter()
+            .map(|matrix| DMat4::from(*matrix).into())
+            .collect()
+    }
+
+    /// All simulated frames, in order.
+    pub fn frames(&self) -> &[SimulationFrame] {
+        &self.frames
+    }
+
+    /// Applies given step to the gizmo.
+    /// Targets are updated with the transforms returned by the gizmo.
+    pub fn step(&mut self, step: SimulationStep) -> &SimulationFrame {
+        let result = match &step {
+            SimulationStep::Update(interaction) => {
+                let updated = self.gizmo.update(*interaction, &self.targets);
+                self.apply_update(updated)
+            }
+            SimulationStep::UpdateWithParents(interaction, parents) => {
+                let updated = self
+                    .gizmo
+                    .update_with_parents(*interaction, &self.targets, parents);
+                self.apply_update(updated)
+            }
+            SimulationStep::UpdateAffine(interaction) => {
+                let updated = self.gizmo.update_affine(*interaction, &self.matrices());
+
+                if self.gizmo.drag_phase() == Some(GizmoDragPhase::Started) {
+                    self.affine_interaction = true;
+                }
+
+                updated.map(|(result, matrices)| {
+                    self.set_matrices(matrices);
+                    result
+                })
+            }
+            SimulationStep::TypeKey(key) => {
+                self.gizmo.type_key(*key);
+                None
+            }
+            SimulationStep::Cancel => {
+                if self.affine_interaction {
+                    if let Some(matrices) = self.gizmo.cancel_affine() {
+                        self.set_matrices(matrices);
+                    }
+                } else if let Some(targets) = self.gizmo.cancel() {
+                    self.set_targets(targets);
+                }
+                None
+            }
+            SimulationStep::UpdateConfig(config) => {
+                self.gizmo.update_config(**config);
+                None
+            }
+            SimulationStep::SetTargetLimits(limits) => {
+                self.gizmo.set_target_limits(limits);
+                None
+            }
+        };
+
+        self.frames.push(SimulationFrame {
+            step,
+            result,
+            drag_phase: self.gizmo.drag_phase(),
+            targets: self.targets.clone(),
+        });
+
+        &self.frames[self.frames.len() - 1]
+    }
+
+    /// Updates the gizmo with given interaction, see [`SimulationStep::Update`].
+    pub fn update(&mut self, interaction: GizmoInteraction) -> &SimulationFrame {
+        self.step(SimulationStep::Update(interaction))
+    }
+
+    /// Types the characters of given text, see [`SimulationStep::TypeKey`].
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.step(SimulationStep::TypeKey(GizmoKey::Char(c)));
+        }
+    }
+
+    /// Cancels the active interaction, see [`SimulationStep::Cancel`].
+    pub fn cancel(&mut self) -> &SimulationFrame {
+        self.step(SimulationStep::Cancel)
+    }
+
+    /// Changes the configuration of the gizmo, see [`SimulationStep::UpdateConfig`].
+    pub fn update_config(&mut self, config: GizmoConfig) -> &SimulationFrame {
+        self.step(SimulationStep::UpdateConfig(Box::new(config)))
+    }
+
+    /// Sets the range limits of the targets, see [`SimulationStep::SetTargetLimits`].
+    pub fn set_target_limits(&mut self, limits: &[TransformLimits]) -> &SimulationFrame {
+        self.step(SimulationStep::SetTargetLimits(limits.to_vec()))
+    }
+
+    /// Drags the pointer from one viewport position to another, in `steps` updates.
+    /// The pointer is pressed at `from` and released at `to`.
+    ///
+    /// Returns the latest result of the drag, if any.
+    pub fn drag(&mut self, from: (f32, f32), to: (f32, f32), steps: usize) -> Option<GizmoResult> {
+        self.drag_with(from, to, steps, SimulationStep::Update)
+    }
+
+    /// Drags the pointer like [`Simulation::drag`], updating the gizmo
+    /// with the steps created by `update` from each interaction, such as
+    /// [`SimulationStep::UpdateWithParents`] or [`SimulationStep::UpdateAffine`].
+    pub fn drag_with(
+        &mut self,
+        from: (f32, f32),
+        to: (f32, f32),
+        steps: usize,
+        update: impl Fn(GizmoInteraction) -> SimulationStep,
+    ) -> Option<GizmoResult> {
+        let from = Pos2::from(from);
+        let to = Pos2::from(to);
+
+        let mut latest_result = self
+            .step(update(GizmoInteraction {
+                cursor_pos: from.into(),
+                hovered: true,
+                drag_started: true,
+                dragging: true,
+            }))
+            .result;
+
+        for step in 1..=steps {
+            let cursor_pos = from.lerp(to, step as f32 / steps as f32);
+            let frame = self.step(update(GizmoInteraction {
+                cursor_pos: cursor_pos.into(),
+                hovered: true,
+                drag_started: false,
+                dragging: true,
+            }));
+            latest_result = frame.result.or(latest_result);
+        }
+
+        let frame = self.step(update(GizmoInteraction {
+            cursor_pos: to.into(),
+            hovered: true,
+            drag_started: false,
+            dragging: false,
+        }));
+
+        frame.result.or(latest_result)
+    }
+
+    /// Viewport position of a point in world space, if it is in front of the camera.
+    pub fn world_to_screen(&self, point: mint::Vector3<f64>) -> Option<(f32, f32)> {
+        let config = self.gizmo.config();
+        let view_projection = config.clip_space.normalizing_matrix()
+            * DMat4::from(config.projection_matrix)
+            * DMat4::from(config.view_matrix);
+
+        world_to_screen(config.viewport, view_projection, point.into()).map(Into::into)
+    }
+
+    /// Recording of the simulated frames, starting from the initial configuration and targets.
+    pub fn recording(&self) -> Recording {
+        Recording {
+            config: self.initial_config,
+            targets: self.initial_targets.clone(),
+            frames: self
+                .frames
+                .iter()
+                .map(|frame| RecordedFrame {
+                    step: frame.step.clone(),
+                    result: frame.result,
+                    targets: frame.targets.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn apply_update(
+        &mut self,
+        updated: Option<(GizmoResult, Vec<Transform>)>,
+    ) -> Option<GizmoResult> {
+        if self.gizmo.drag_phase() == Some(GizmoDragPhase::Started) {
+            self.affine_interaction = false;
+        }
+
+        let (result, targets) = updated?;
+        self.set_targets(targets);
+        Some(result)
+    }
+
+    fn set_targets(&mut self, targets: Vec<Transform>) {
+        self.matrices = targets
+            .iter()
+            .map(|target| {
+                DAffine3::from_scale_rotation_translation(
+                    target.scale.into(),
+                    target.rotation.into(),
+                    target.translation.into(),
+                )
+            })
+            .collect();
+        self.targets = targets;
+    }
+
+    fn set_matrices(&mut self, matrices: Vec<mint::ColumnMatrix4<f64>>) {
+        let matrices = matrices
+            .into_iter()
+            .map(|matrix| DAffine3::from_mat4(matrix.into()))
+            .collect::<Vec<_>>();
+
+        self.targets = matrices
+            .iter()
+            .map(|matrix| {
+                let (scale, rotation, translation) = decompose_affine(*matrix);
+                Transform::from_scale_rotation_translation(scale, rotation, translation)
+            })
+            .collect();
+        self.matrices = matrices;
+    }
+}
+
+/// A scripted call on the simulated gizmo.
+#[derive(Debug, Clone)]
+pub enum SimulationStep {
+    /// [`Gizmo::update`] with the current targets
+    Update(GizmoInteraction),
+    /// [`Gizmo::update_with_parents`] with the current targets, which are in the local
+    /// space of the given world space parent matrices
+    UpdateWithParents(GizmoInteraction, Vec<mint::ColumnMatrix4<f64>>),
+    /// [`Gizmo::update_affine`] with the current targets as matrices.
+    /// The targets are decomposed from the updated matrices, see [`Simulation::matrices`].
+    UpdateAffine(GizmoInteraction),
+    /// [`Gizmo::type_key`]
+    TypeKey(GizmoKey),
+    /// [`Gizmo::cancel`], or [`Gizmo::cancel_affine`] if the interaction was updated with
+    /// [`SimulationStep::UpdateAffine`]. The targets are restored to the start of the interaction.
+    Cancel,
+    /// [`Gizmo::update_config`]
+    UpdateConfig(Box<GizmoConfig>),
+    /// [`Gizmo::set_target_limits`]
+    SetTargetLimits(Vec<TransformLimits>),
+}
+
+/// A single simulated step of the gizmo.
+#[derive(Debug, Clone)]
+pub struct SimulationFrame {
+    /// Step applied to the gizmo
+    pub step: SimulationStep,
+    /// Result returned by the gizmo
+    pub result: Option<GizmoResult>,
+    /// Drag phase of the gizmo after the step
+    pub drag_phase: Option<GizmoDragPhase>,
+    /// Transforms of the targets after the step
+    pub targets: Vec<Transform>,
+}
+
+/// Recorded steps of a gizmo and the resulting transforms and results.
+///
+/// Recordings are written with [`Display`] and parsed with [`FromStr`].
+/// The text format stores every setting of the configuration, including the visuals,
+/// and the configurations and limits given in the steps.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    /// Configuration of the gizmo before the first frame
+    pub config: GizmoConfig,
+    /// Transforms of the targets before the first frame
+    pub targets: Vec<Transform>,
+    /// Recorded frames, in order
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    /// Replays the recorded steps in a new simulation.
+    pub fn replay(&self) -> Simulation {
+        let mut simulation = Simulation::new(self.config, &self.targets);
+        for frame in &self.frames {
+            simulation.step(frame.step.clone());
+        }
+        simulation
+    }
+
+    /// Replays the recorded steps and compares the resulting transforms and results
+    /// to the recorded ones. Transforms match if their matrices differ by at most
+    /// `tolerance` per element, and results match if their values do.
+    ///
+    /// Returns the first mismatch, if any.
+    pub fn first_mismatch(&self, tolerance: f64) -> Option<ReplayMismatch> {
+        let simulation = self.replay();
+
+        for (frame_index, (recorded, simulated)) in
+            self.frames.iter().zip(simulation.frames()).enumerate()
+        {
+            let results_match = match (recorded.result, simulated.result) {
+                (Some(expected), Some(actual)) => results_match(expected, actual, tolerance),
+                (expected, actual) => expected.is_none() && actual.is_none(),
+            };
+
+            if !results_match {
+                return Some(ReplayMismatch::Result {
+                    frame: frame_index,
+                    expected: recorded.result,
+                    actual: simulated.result,
+                });
+            }
+
+            let mismatch = |target, expected, actual| ReplayMismatch::Target {
+                frame: frame_index,
+                target,
+                expected,
+                actual,
+            };
+
+            if recorded.targets.len() != simulated.targets.len() {
+                return Some(mismatch(
+                    recorded.targets.len().min(simulated.targets.len()),
+                    recorded.targets.get(simulated.targets.len()).copied(),
+                    simulated.targets.get(recorded.targets.len()).copied(),
+                ));
+            }
+
+            for (target, (expected, actual)) in
+                recorded.targets.iter().zip(&simulated.targets).enumerate()
+            {
+                if !transform_matrix(*expected).abs_diff_eq(transform_matrix(*actual), tolerance) {
+                    return Some(mismatch(target, Some(*expected), Some(*actual)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// A single recorded step of the gizmo.
+#[derive(Debug, Clone)]
+pub struct RecordedFrame {
+    /// Step applied to the gizmo
+    pub step: SimulationStep,
+    /// Result returned by the gizmo
+    pub result: Option<GizmoResult>,
+    /// Transforms of the targets after the step
+    pub targets: Vec<Transform>,
+}
+
+/// Difference between a replayed and a recorded frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayMismatch {
+    /// The result returned by the gizmo differs
+    Result {
+        /// Index of the frame
+        frame: usize,
+        /// Recorded result
+        expected: Option<GizmoResult>,
+        /// Replayed result
+        actual: Option<GizmoResult>,
+    },
+    /// The transform of a target differs
+    Target {
+        /// Index of the frame
+        frame: usize,
+        /// Index of the target
+        target: usize,
+        /// Recorded transform of the target, if it was recorded
+        expected: Option<Transform>,
+        /// Replayed transform of the target, if it was replayed
+        actual: Option<Transform>,
+    },
+}
+
+impl Display for ReplayMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Result {
+                frame,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "result of frame {frame} does not match the recording: expected {expected:?}, got {actual:?}"
+            ),
+            Self::Target {
+                frame,
+                target,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "target {target} of frame {frame} does not match the recording: expected {expected:?}, got {actual:?}"
+            ),
+        }
+    }
+}
+
+/// Error in the text format of a [`Recording`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingError {
+    /// Line of the error, starting from 1
+    pub line: usize,
+    /// Description of the error
+    pub message: String,
+}
+
+impl Display for RecordingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+impl Display for Recording {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{RECORDING_HEADER}")?;
+        write_config(f, &self.config)?;
+        write_targets(f, &self.targets)?;
+
+        for frame in &self.frames {
+            write_step(f, &frame.step)?;
+            if let Some(result) = frame.result {
+                write_result(f, result)?;
+            }
+            write_targets(f, &frame.targets)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Recording {
+    type Err = RecordingError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        match lines.next() {
+            Some((_, RECORDING_HEADER)) => {}
+            Some((line, _)) => return Err(error(line, "unsupported recording format")),
+            None => return Err(error(1, "empty recording")),
+        }
+
+        let mut recording = Self {
+            config: GizmoConfig::default(),
+            targets: Vec::new(),
+            frames: Vec::new(),
+        };
+
+        for (line, text) in lines {
+            let mut words = Words(text.split_whitespace());
+            let key = words.0.next().unwrap_or_default();
+
+            parse_line(&mut recording, key, &mut words)
+                .and_then(|()| words.finish())
+                .map_err(|message| error(line, &message))?;
+        }
+
+        Ok(recording)
+    }
+}
+
+fn parse_line(recording: &mut Recording, key: &str, words: &mut Words<'_>) -> Result<(), String> {
+    let step = match key {
+        "update" => SimulationStep::Update(parse_interaction(words)?),
+        "update_with_parents" => {
+            SimulationStep::UpdateWithParents(parse_interaction(words)?, Vec::new())
+        }
+        "update_affine" => SimulationStep::UpdateAffine(parse_interaction(words)?),
+        "type_key" => SimulationStep::TypeKey(match words.next()? {
+            "Char" => GizmoKey::Char(
+                char::from_u32(words.value()?).ok_or_else(|| "invalid character".to_owned())?,
+            ),
+            "Backspace" => GizmoKey::Backspace,
+            "Enter" => GizmoKey::Enter,
+            name => return Err(format!("unknown key `{name}`")),
+        }),
+        "cancel" => SimulationStep::Cancel,
+        "update_config" => SimulationStep::UpdateConfig(Box::default()),
+        "set_target_limits" => SimulationStep::SetTargetLimits(Vec::new()),
+        _ => return parse_frame_line(recording, key, words),
+    };
+
+    recording.frames.push(RecordedFrame {
+        step,
+        result: None,
+        targets: Vec::new(),
+    });
+
+    Ok(())
+}
+
+/// Parses a line that belongs to the latest frame, or to the start of the recording.
+fn parse_frame_line(
+    recording: &mut Recording,
+    key: &str,
+    words: &mut Words<'_>,
+) -> Result<(), String> {
+    let frame = recording.frames.last_mut();
+
+    match key {
+        "target" => {
+            let target = parse_target(words)?;
+
+            // Targets before the first frame are the initial targets
+            match frame {
+                Some(frame) => frame.targets.push(target),
+                None => recording.targets.push(target),
+            }
+        }
+        "result" => {
+            let frame = frame.ok_or_else(|| "result before the first frame".to_owned())?;
+            frame.result = Some(parse_result(words)?);
+        }
+        "parent" => match frame.map(|frame| &mut frame.step) {
+            Some(SimulationStep::UpdateWithParents(_, parents)) => {
+                parents.push(DMat4::from_cols_array(&words.values()?).into());
+            }
+            _ => return Err("parent outside of an `update_with_parents` frame".to_owned()),
+        },
+        "limits" => match frame.map(|frame| &mut frame.step) {
+            Some(SimulationStep::SetTargetLimits(limits)) => limits.push(parse_limits(words)?),
+            _ => return Err("limits outside of a `set_target_limits` frame".to_owned()),
+        },
+        _ => {
+            // Configuration lines before the first frame are the initial configuration
+            let config = match frame.map(|frame| &mut frame.step) {
+                None => &mut recording.config,
+                Some(SimulationStep::UpdateConfig(config)) => &mut **config,
+                Some(_) => return Err(format!("unknown key `{key}` outside of the configuration")),
+            };
+
+            if !parse_config_line(config, key, words)? {
+                return Err(format!("unknown key `{key}`"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a line of the configuration. Returns false if the key is not a configuration key.
+fn parse_config_line(
+    config: &mut GizmoConfig,
+    key: &str,
+    words: &mut Words<'_>,
+) -> Result<bool, String> {
+    match key {
+        "view_matrix" => config.view_matrix = DMat4::from_cols_array(&words.values()?).into(),
+        "projection_matrix" => {
+            config.projection_matrix = DMat4::from_cols_array(&words.values()?).into();
+        }
+        "clip_space" => {
+            config.clip_space = ClipSpace {
+                depth_range: words.name([DepthRange::NegativeOneToOne, DepthRange::ZeroToOne])?,
+                reversed_z: words.value()?,
+                y_down: words.value()?,
+            };
+        }
+        "viewport" => {
+            let [min_x, min_y, max_x, max_y] = words.values()?;
+            config.viewport = Rect::from_min_max(Pos2::new(min_x, min_y), Pos2::new(max_x, max_y));
+        }
+        "origin_offset" => config.origin_offset = words.vector()?,
+        "modes" => config.modes = words.names()?,
+        "mode_override" => {
+            config.mode_override = words.option(|words| words.name(EnumSet::<GizmoMode>::all()))?;
+        }
+        "orientation" => {
+            config.orientation = match words.next()? {
+                "Gimbal" => GizmoOrientation::Gimbal(words.name(EULER_ORDERS)?),
+                orientation => parse_name(
+                    [
+                        GizmoOrientation::Global,
+                        GizmoOrientation::Local,
+                        GizmoOrientation::View,
+                        GizmoOrientation::Parent,
+                        GizmoOrientation::Custom,
+                    ],
+                    orientation,
+                )?,
+            };
+        }
+        "parent_rotation" => {
+            config.parent_rotation = DQuat::from_array(words.values()?).into();
+        }
+        "custom_orientation" => {
+            config.custom_orientation = DQuat::from_array(words.values()?).into();
+        }
+        "pivot_point" => {
+            config.pivot_point = parse_name(
+                [
+                    TransformPivotPoint::MedianPoint,
+                    TransformPivotPoint::IndividualOrigins,
+                    TransformPivotPoint::BoundingBoxCenter,
+                    TransformPivotPoint::ActiveElement,
+                    TransformPivotPoint::Point,
+                ],
+                words.next()?,
+            )?;
+        }
+        "pivot_position" => config.pivot_position = words.vector()?,
+        "locked_translation" => config.locked_translation = words.names()?,
+        "locked_rotation" => config.locked_rotation = words.names()?,
+        "locked_scale" => config.locked_scale = words.names()?,
+        "bounds" => config.bounds = words.bounds()?,
+        "edit_pivot" => config.edit_pivot = words.value()?,
+        "snapping" => config.snapping = words.value()?,
+        "snap_mode" => config.snap_mode = words.name([SnapMode::Relative, SnapMode::Absolute])?,
+        "snap_angle" => config.snap_angle = words.value()?,
+        "snap_distance" => config.snap_distance = words.value()?,
+        "snap_scale" => config.snap_scale = words.value()?,
+        "snap_angle_per_axis" => config.snap_angle_per_axis = words.option(Words::vector)?,
+        "snap_distance_per_axis" => config.snap_distance_per_axis = words.option(Words::vector)?,
+        "snap_scale_per_axis" => config.snap_scale_per_axis = words.option(Words::vector)?,
+        "surface_align_axis" => {
+            config.surface_align_axis =
+                words.option(|words| words.name(EnumSet::<GizmoDirection>::all()))?;
+        }
+        "snap_target_radius" => config.snap_target_radius = words.value()?,
+        "pixels_per_point" => config.pixels_per_point = words.value()?,
+        _ => return parse_visuals_line(&mut config.visuals, key, words),
+    }
+
+    Ok(true)
+}
+
+/// Parses a line of the visuals. Returns false if the key is not a visuals key.
+fn parse_visuals_line(
+    visuals: &mut GizmoVisuals,
+    key: &str,
+    words: &mut Words<'_>,
+) -> Result<bool, String> {
+    match key {
+        "visuals.x_color" => visuals.x_color = words.color()?,
+        "visuals.y_color" => visuals.y_color = words.color()?,
+        "visuals.z_color" => visuals.z_color = words.color()?,
+        "visuals.s_color" => visuals.s_color = words.color()?,
+        "visuals.inactive_alpha" => visuals.inactive_alpha = words.value()?,
+        "visuals.highlight_alpha" => visuals.highlight_alpha = words.value()?,
+        "visuals.inactive_color" => visuals.inactive_color = words.option(Words::color)?,
+        "visuals.highlight_color" => visuals.highlight_color = words.option(Words::color)?,
+        "visuals.active_color" => visuals.active_color = words.option(Words::color)?,
+        "visuals.disabled_color" => visuals.disabled_color = words.color()?,
+        "visuals.stroke_width" => visuals.stroke_width = words.value()?,
+        "visuals.gizmo_size" => visuals.gizmo_size = words.value()?,
+        "visuals.axis_labels" => visuals.axis_labels = words.value()?,
+        "visuals.label_size" => visuals.label_size = words.value()?,
+        "visuals.value_readout" => visuals.value_readout = words.value()?,
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+fn parse_interaction(words: &mut Words<'_>) -> Result<GizmoInteraction, String> {
+    Ok(GizmoInteraction {
+        cursor_pos: (words.value()?, words.value()?),
+        hovered: words.value()?,
+        drag_started: words.value()?,
+        dragging: words.value()?,
+    })
+}
+
+fn parse_target(words: &mut Words<'_>) -> Result<Transform, String> {
+    Ok(Transform::from_scale_rotation_translation(
+        words.vector::<f64>()?,
+        DQuat::from_array(words.values()?),
+        words.vector::<f64>()?,
+    ))
+}
+
+fn parse_limits(words: &mut Words<'_>) -> Result<TransformLimits, String> {
+    Ok(TransformLimits {
+        translation: words.option(Words::bounds)?,
+        min_rotation: words.option(Words::vector)?,
+        max_rotation: words.option(Words::vector)?,
+        rotation_order: words.name(EULER_ORDERS)?,
+        min_scale: words.option(Words::vector)?,
+        max_scale: words.option(Words::vector)?,
+    })
+}
+
+fn parse_result(words: &mut Words<'_>) -> Result<GizmoResult, String> {
+    Ok(match words.next()? {
+        "Rotation" => GizmoResult::Rotation {
+            axis: words.vector()?,
+            delta: words.value()?,
+            total: words.value()?,
+            is_view_axis: words.value()?,
+            euler_channel: words.option(|words| words.name(EnumSet::<GizmoDirection>::all()))?,
+        },
+        "Translation" => GizmoResult::Translation {
+            delta: words.vector()?,
+            total: words.vector()?,
+        },
+        "Scale" => GizmoResult::Scale {
+            total: words.vector()?,
+        },
+        "Arcball" => GizmoResult::Arcball {
+            delta: DQuat::from_array(words.values()?).into(),
+            total: DQuat::from_array(words.values()?).into(),
+        },
+        "Surface" => GizmoResult::Surface {
+            delta: words.vector()?,
+            total: words.vector()?,
+            normal: words.vector()?,
+        },
+        "BoundsScale" => GizmoResult::BoundsScale {
+            total: words.vector()?,
+            anchor: words.vector()?,
+        },
+        name => return Err(format!("unknown result `{name}`")),
+    })
+}
+
+fn error(line: usize, message: &str) -> RecordingError {
+    RecordingError {
+        line,
+        message: message.to_owned(),
+    }
+}
+
+/// Values of a line in the text format, parsed in order.
+struct Words<'a>(SplitWhitespace<'a>);
+
+impl<'a> Words<'a> {
+    fn next(&mut self) -> Result<&'a str, String> {
+        self.0.next().ok_or_else(|| "missing value".to_owned())
+    }
+
+    fn value<T: FromStr>(&mut self) -> Result<T, String>
+    where
+        T::Err: Display,
+    {
+        parse_value(self.next()?)
+    }
+
+    fn values<T, const N: usize>(&mut self) -> Result<[T; N], String>
+    where
+        T: FromStr + Default + Copy,
+        T::Err: Display,
+    {
+        let mut values = [T::default(); N];
+        for value in &mut values {
+            *value = self.value()?;
+        }
+        Ok(values)
+    }
+
+    fn vector<T>(&mut self) -> Result<mint::Vector3<T>, String>
+    where
+        T: FromStr + Default + Copy,
+        T::Err: Display,
+    {
+        self.values().map(mint::Vector3::from)
+    }
+
+    fn bounds(&mut self) -> Result<GizmoBounds, String> {
+        Ok(GizmoBounds {
+            min: self.vector()?,
+            max: self.vector()?,
+        })
+    }
+
+    fn color(&mut self) -> Result<Color32, String> {
+        let [r, g, b, a] = self.values()?;
+        Ok(Color32::from_rgba_premultiplied(r, g, b, a))
+    }
+
+    fn name<T: Debug>(&mut self, values: impl IntoIterator<Item = T>) -> Result<T, String> {
+        parse_name(values, self.next()?)
+    }
+
+    /// Parses the remaining values as names of set members.
+    fn names<T: EnumSetType + Debug>(&mut self) -> Result<EnumSet<T>, String> {
+        self.0
+            .by_ref()
+            .map(|name| parse_name(EnumSet::<T>::all(), name))
+            .collect()
+    }
+
+    /// Parses `None`, or `Some` followed by the value.
+    fn option<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<Option<T>, String> {
+        match self.next()? {
+            "None" => Ok(None),
+            "Some" => parse(self).map(Some),
+            word => Err(format!("expected `None` or `Some`, got `{word}`")),
+        }
+    }
+
+    fn finish(mut self) -> Result<(), String> {
+        match self.0.next() {
+            Some(word) => Err(format!("unexpected value `{word}`")),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_value<T: FromStr>(word: &str) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    word.parse()
+        .map_err(|err| format!("invalid value `{word}`: {err}"))
+}
+
+/// Finds the value whose [`Debug`] name matches the given name.
+fn parse_name<T: Debug>(values: impl IntoIterator<Item = T>, name: &str) -> Result<T, String> {
+    values
+        .into_iter()
+        .find(|value| format!("{value:?}") == name)
+        .ok_or_else(|| format!("unknown name `{name}`"))
+}
+
+fn write_config(f: &mut Formatter<'_>, config: &GizmoConfig) -> std::fmt::Result {
+    // Destructured so that new settings cannot be left out of the text format
+    let GizmoConfig {
+        view_matrix,
+        projection_matrix,
+        clip_space,
+        viewport,
+        origin_offset,
+        modes,
+        mode_override,
+        orientation,
+        parent_rotation,
+        custom_orientation,
+        pivot_point,
+        pivot_position,
+        locked_translation,
+        locked_rotation,
+        locked_scale,
+        bounds,
+        edit_pivot,
+        snapping,
+        snap_mode,
+        snap_angle,
+        snap_distance,
+        snap_scale,
+        snap_angle_per_axis,
+        snap_distance_per_axis,
+        snap_scale_per_axis,
+        surface_align_axis,
+        snap_target_radius,
+        visuals,
+        pixels_per_point,
+    } = *config;
+
+    write_line(f, "view_matrix", &DMat4::from(view_matrix).to_cols_array())?;
+    write_line(
+        f,
+        "projection_matrix",
+        &DMat4::from(projection_matrix).to_cols_array(),
+    )?;
+    writeln!(
+        f,
+        "clip_space {:?} {} {}",
+        clip_space.depth_range, clip_space.reversed_z, clip_space.y_down
+    )?;
+    write_line(
+        f,
+        "viewport",
+        &[
+            viewport.min.x,
+            viewport.min.y,
+            viewport.max.x,
+            viewport.max.y,
+        ],
+    )?;
+    write!(f, "origin_offset")?;
+    write_vector(f, origin_offset)?;
+    writeln!(f)?;
+
+    write!(f, "modes")?;
+    write_names(f, modes)?;
+    writeln!(f)?;
+    write!(f, "mode_override")?;
+    write_option(f, mode_override, |f, mode| write_names(f, [mode]))?;
+    writeln!(f)?;
+
+    match orientation {
+        GizmoOrientation::Gimbal(order) => writeln!(f, "orientation Gimbal {order:?}")?,
+        orientation => writeln!(f, "orientation {orientation:?}")?,
+    }
+    write_line(
+        f,
+        "parent_rotation",
+        &DQuat::from(parent_rotation).to_array(),
+    )?;
+    write_line(
+        f,
+        "custom_orientation",
+        &DQuat::from(custom_orientation).to_array(),
+    )?;
+
+    writeln!(f, "pivot_point {pivot_point:?}")?;
+    write!(f, "pivot_position")?;
+    write_vector(f, pivot_position)?;
+    writeln!(f)?;
+
+    for (key, locked) in [
+        ("locked_translation", locked_translation),
+        ("locked_rotation", locked_rotation),
+        ("locked_scale", locked_scale),
+    ] {
+        write!(f, "{key}")?;
+        write_names(f, locked)?;
+        writeln!(f)?;
+    }
+
+    write!(f, "bounds")?;
+    write_bounds(f, bounds)?;
+    writeln!(f)?;
+    writeln!(f, "edit_pivot {edit_pivot}")?;
+
+    writeln!(f, "snapping {snapping}")?;
+    writeln!(f, "snap_mode {snap_mode:?}")?;
+    writeln!(f, "snap_angle {snap_angle}")?;
+    writeln!(f, "snap_distance {snap_distance}")?;
+    writeln!(f, "snap_scale {snap_scale}")?;
+    for (key, increments) in [
+        ("snap_angle_per_axis", snap_angle_per_axis),
+        ("snap_distance_per_axis", snap_distance_per_axis),
+        ("snap_scale_per_axis", snap_scale_per_axis),
+    ] {
+        write!(f, "{key}")?;
+        write_option(f, increments, write_vector)?;
+        writeln!(f)?;
+    }
+    write!(f, "surface_align_axis")?;
+    write_option(f, surface_align_axis, |f, axis| write_names(f, [axis]))?;
+    writeln!(f)?;
+    writeln!(f, "snap_target_radius {snap_target_radius}")?;
+    writeln!(f, "pixels_per_point {pixels_per_point}")?;
+
+    write_visuals(f, &visuals)
+}
+
+fn write_visuals(f: &mut Formatter<'_>, visuals: &GizmoVisuals) -> std::fmt::Result {
+    // Destructured so that new settings cannot be left out of the text format
+    let GizmoVisuals {
+        x_color,
+        y_color,
+        z_color,
+        s_color,
+        inactive_alpha,
+        highlight_alpha,
+        inactive_color,
+        highlight_color,
+        active_color,
+        disabled_color,
+        stroke_width,
+        gizmo_size,
+        axis_labels,
+        label_size,
+        value_readout,
+    } = *visuals;
+
+    for (key, color) in [
+        ("x_color", x_color),
+        ("y_color", y_color),
+        ("z_color", z_color),
+        ("s_color", s_color),
+        ("disabled_color", disabled_color),
+    ] {
+        write_line(f, &format!("visuals.{key}"), &color.to_array())?;
+    }
+    for (key, color) in [
+        ("inactive_color", inactive_color),
+        ("highlight_color", highlight_color),
+        ("active_color", active_color),
+    ] {
+        write!(f, "visuals.{key}")?;
+        write_option(f, color, |f, color| write_values(f, &color.to_array()))?;
+        writeln!(f)?;
+    }
+
+    writeln!(f, "visuals.inactive_alpha {inactive_alpha}")?;
+    writeln!(f, "visuals.highlight_alpha {highlight_alpha}")?;
+    writeln!(f, "visuals.stroke_width {stroke_width}")?;
+    writeln!(f, "visuals.gizmo_size {gizmo_size}")?;
+    writeln!(f, "visuals.axis_labels {axis_labels}")?;
+    writeln!(f, "visuals.label_size {label_size}")?;
+    writeln!(f, "visuals.value_readout {value_readout}")
+}
+
+fn write_step(f: &mut Formatter<'_>, step: &SimulationStep) -> std::fmt::Result {
+    match step {
+        SimulationStep::Update(interaction) => write_interaction(f, "update", *interaction),
+        SimulationStep::UpdateWithParents(interaction, parents) => {
+            write_interaction(f, "update_with_parents", *interaction)?;
+            for parent in parents {
+                write_line(f, "parent", &DMat4::from(*parent).to_cols_array())?;
+            }
+            Ok(())
+        }
+        SimulationStep::UpdateAffine(interaction) => {
+            write_interaction(f, "update_affine", *interaction)
+        }
+        SimulationStep::TypeKey(GizmoKey::Char(c)) => {
+            // Characters are written as code points, as they may be whitespace
+            writeln!(f, "type_key Char {}", u32::from(*c))
+        }
+        SimulationStep::TypeKey(key) => writeln!(f, "type_key {key:?}"),
+        SimulationStep::Cancel => writeln!(f, "cancel"),
+        SimulationStep::UpdateConfig(config) => {
+            writeln!(f, "update_config")?;
+            write_config(f, config)
+        }
+        SimulationStep::SetTargetLimits(limits) => {
+            writeln!(f, "set_target_limits")?;
+            for limits in limits {
+                write_limits(f, limits)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_interaction(
+    f: &mut Formatter<'_>,
+    key: &str,
+    interaction: GizmoInteraction,
+) -> std::fmt::Result {
+    writeln!(
+        f,
+        "{key} {} {} {} {} {}",
+        interaction.cursor_pos.0,
+        interaction.cursor_pos.1,
+        interaction.hovered,
+        interaction.drag_started,
+        interaction.dragging
+    )
+}
+
+fn write_limits(f: &mut Formatter<'_>, limits: &TransformLimits) -> std::fmt::Result {
+    let TransformLimits {
+        translation,
+        min_rotation,
+        max_rotation,
+        rotation_order,
+        min_scale,
+        max_scale,
+    } = *limits;
+
+    write!(f, "limits")?;
+    write_option(f, translation, write_bounds)?;
+    write_option(f, min_rotation, write_vector)?;
+    write_option(f, max_rotation, write_vector)?;
+    write!(f, " {rotation_order:?}")?;
+    write_option(f, min_scale, write_vector)?;
+    write_option(f, max_scale, write_vector)?;
+    writeln!(f)
+}
+
+fn write_result(f: &mut Formatter<'_>, result: GizmoResult) -> std::fmt::Result {
+    let (name, values) = result_values(result);
+    write!(f, "result {name}")?;
+    write_values(f, &values)?;
+
+    if let GizmoResult::Rotation {
+        is_view_axis,
+        euler_channel,
+        ..
+    } = result
+    {
+        write!(f, " {is_view_axis}")?;
+        write_option(f, euler_channel, |f, channel| write_names(f, [channel]))?;
+    }
+
+    writeln!(f)
+}
+
+fn write_targets(f: &mut Formatter<'_>, targets: &[Transform]) -> std::fmt::Result {
+    for target in targets {
+        write!(f, "target")?;
+        write_vector(f, target.scale)?;
+        write_values(f, &DQuat::from(target.rotation).to_array())?;
+        write_vector(f, target.translation)?;
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+/// Writes a line with given key and values.
+fn write_line<T: Display>(f: &mut Formatter<'_>, key: &str, values: &[T]) -> std::fmt::Result {
+    write!(f, "{key}")?;
+    write_values(f, values)?;
+    writeln!(f)
+}
+
+fn write_values<T: Display>(f: &mut Formatter<'_>, values: &[T]) -> std::fmt::Result {
+    for value in values {
+        write!(f, " {value}")?;
+    }
+    Ok(())
+}
+
+fn write_vector<T: Display>(f: &mut Formatter<'_>, vector: mint::Vector3<T>) -> std::fmt::Result {
+    write_values(f, &[vector.x, vector.y, vector.z])
+}
+
+fn write_bounds(f: &mut Formatter<'_>, bounds: GizmoBounds) -> std::fmt::Result {
+    write_vector(f, bounds.min)?;
+    write_vector(f, bounds.max)
+}
+
+fn write_names<T: Debug>(
+    f: &mut Formatter<'_>,
+    names: impl IntoIterator<Item = T>,
+) -> std::fmt::Result {
+    for name in names {
+        write!(f, " {name:?}")?;
+    }
+    Ok(())
+}
+
+fn write_option<T>(
+    f: &mut Formatter<'_>,
+    value: Option<T>,
+    write: impl FnOnce(&mut Formatter<'_>, T) -> std::fmt::Result,
+) -> std::fmt::Result {
+    match value {
+        Some(value) => {
+            write!(f, " Some")?;
+            write(f, value)
+        }
+        None => write!(f, " None"),
+    }
+}
+
+/// Name and numeric values of a result, as written in the text format.
+fn result_values(result: GizmoResult) -> (&'static str, Vec<f64>) {
+    let vector = |vector: mint::Vector3<f64>| [vector.x, vector.y, vector.z];
+    let quat = |quat: mint::Quaternion<f64>| DQuat::from(quat).to_array();
+
+    match result {
+        GizmoResult::Rotation {
+            axis, delta, total, ..
+        } => ("Rotation", [&vector(axis)[..], &[delta, total]].concat()),
+        GizmoResult::Translation { delta, total } => {
+            ("Translation", [vector(delta), vector(total)].concat())
+        }
+        GizmoResult::Scale { total } => ("Scale", vector(total).to_vec()),
+        GizmoResult::Arcball { delta, total } => ("Arcball", [quat(delta), quat(total)].concat()),
+        GizmoResult::Surface {
+            delta,
+            total,
+            normal,
+        } => (
+            "Surface",
+            [vector(delta), vector(total), vector(normal)].concat(),
+        ),
+        GizmoResult::BoundsScale { total, anchor } => {
+            ("BoundsScale", [vector(total), vector(anchor)].concat())
+        }
+    }
+}
+
+/// Whether the results are of the same kind and their values differ by at most `tolerance`.
+fn results_match(expected: GizmoResult, actual: GizmoResult, tolerance: f64) -> bool {
+    let flags = |result: GizmoResult| match result {
+        GizmoResult::Rotation {
+            is_view_axis,
+            euler_channel,
+            ..
+        } => Some((is_view_axis, euler_channel)),
+        _ => None,
+    };
+
+    let (expected_name, expected_values) = result_values(expected);
+    let (actual_name, actual_values) = result_values(actual);
+
+    expected_name == actual_name
+        && flags(expected) == flags(actual)
+        && expected_values
+            .iter()
+            .zip(&actual_values)
+            .all(|(expected, actual)| (expected - actual).abs() <= tolerance)
+}
+
+fn transform_matrix(transform: Transform) -> DMat4 {
+    DMat4::from_scale_rotation_translation(
+        transform.scale.into(),
+        transform.rotation.into(),
+        transform.translation.into(),
+    )
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,698 @@
+use std::f64::consts::FRAC_PI_2;
+
+use glam::{DMat3, DVec3, DVec4};
+
+use super::*;
+
+fn assert_vec_eq(actual: impl Into<DVec3>, expected: DVec3) {
+    let actual = actual.into();
+    assert!(
+        actual.abs_diff_eq(expected, 1e-6),
+        "expected {expected}, got {actual}"
+    );
+}
+
+/// Viewport position of the X axis arrow of a gizmo at the origin
+const X_ARROW: (f32, f32) = (450.0, 400.0);
+
+/// Viewport position of the XY plane handle of a gizmo at the origin
+const XY_PLANE: (f32, f32) = (440.0, 360.0);
+
+/// Camera looking at the origin from +Z, with a square viewport
+fn test_config(modes: EnumSet<GizmoMode>) -> GizmoConfig {
+    GizmoConfig {
+        view_matrix: DMat4::look_at_rh(DVec3::Z * 10.0, DVec3::ZERO, DVec3::Y).into(),
+        projection_matrix: DMat4::perspective_rh_gl(1.0, 1.0, 0.1, 100.0).into(),
+        viewport: Rect::from_min_size(Pos2::ZERO, emath::vec2(800.0, 800.0)),
+        modes,
+        ..Default::default()
+    }
+}
+
+/// Configuration where given mode is forced active, so it is picked wherever the pointer is
+fn forced(mode: GizmoMode) -> GizmoConfig {
+    GizmoConfig {
+        mode_override: Some(mode),
+        ..test_config(mode.into())
+    }
+}
+
+fn translated(x: f64, y: f64, z: f64) -> Transform {
+    Transform::from_scale_rotation_translation(DVec3::ONE, DQuat::IDENTITY, DVec3::new(x, y, z))
+}
+
+fn interaction(cursor_pos: (f32, f32), drag_started: bool, dragging: bool) -> GizmoInteraction {
+    GizmoInteraction {
+        cursor_pos,
+        hovered: true,
+        drag_started,
+        dragging,
+    }
+}
+
+/// Presses the pointer, types given text and updates the gizmo with the typed value.
+fn type_value(simulation: &mut Simulation, text: &str) -> Option<GizmoResult> {
+    simulation.update(interaction((400.0, 400.0), true, true));
+    simulation.type_text(text);
+    simulation
+        .update(interaction((400.0, 400.0), false, true))
+        .result
+}
+
+fn release(simulation: &mut Simulation) {
+    simulation.update(interaction((400.0, 400.0), false, false));
+}
+
+#[test]
+fn typed_translation() {
+    let mut simulation =
+        Simulation::new(forced(GizmoMode::TranslateX), &[translated(1.0, 0.0, 0.0)]);
+
+    simulation.update(interaction((400.0, 400.0), true, true));
+    simulation.type_text("25");
+    simulation.step(SimulationStep::TypeKey(GizmoKey::Backspace));
+    simulation.type_text("*1.5");
+    let result = simulation
+        .update(interaction((400.0, 400.0), false, true))
+        .result;
+    release(&mut simulation);
+
+    assert!(matches!(result, Some(GizmoResult::Translation { .. })));
+    assert_vec_eq(
+        simulation.targets()[0].translation,
+        DVec3::new(4.0, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn typed_rotation() {
+    let mut simulation = Simulation::new(forced(GizmoMode::RotateZ), &[Transform::default()]);
+
+    type_value(&mut simulation, "90");
+    release(&mut simulation);
+
+    let rotation = DQuat::from(simulation.targets()[0].rotation);
+    assert!(rotation.abs_diff_eq(DQuat::from_rotation_z(FRAC_PI_2), 1e-9));
+}
+
+#[test]
+fn typed_scale() {
+    let mut simulation = Simulation::new(forced(GizmoMode::ScaleY), &[Transform::default()]);
+
+    type_value(&mut simulation, "3");
+    release(&mut simulation);
+
+    assert_vec_eq(simulation.targets()[0].scale, DVec3::new(1.0, 3.0, 1.0));
+}
+
+#[test]
+fn value_readout_shows_typed_sign() {
+    for (mode, text, readout) in [
+        (GizmoMode::RotateZ, "90", "90.0°"),
+        (GizmoMode::RotateZ, "-45", "-45.0°"),
+        (GizmoMode::TranslateX, "-2", "-2.00"),
+        (GizmoMode::ScaleY, "3", "×1.00, 3.00, 1.00"),
+    ] {
+        let mut simulation = Simulation::new(forced(mode), &[Transform::default()]);
+        let result = type_value(&mut simulation, text).unwrap();
+        let draw_data = simulation.gizmo().draw();
+
+        assert_eq!(result.to_string(), readout);
+        assert_eq!(draw_data.texts.last().unwrap().text, readout);
+    }
+}
+
+#[test]
+fn cancel_restores_start_transforms() {
+    let start = Transform::default();
+    let mut simulation = Simulation::new(test_config(GizmoMode::TranslateX.into()), &[start]);
+
+    simulation.update(interaction(X_ARROW, true, true));
+    simulation.update(interaction((500.0, 400.0), false, true));
+    assert!(simulation.targets()[0].translation.x > 0.5);
+
+    let frame = simulation.cancel();
+    assert_eq!(frame.drag_phase, Some(GizmoDragPhase::Cancelled));
+    assert_eq!(simulation.targets(), &[start]);
+}
+
+#[test]
+fn cancel_with_parents_restores_local_transforms() {
+    // The parent places the target at the world origin
+    let start = translated(1.0, 0.0, 0.0);
+    let parent = DMat4::from_scale_rotation_translation(
+        DVec3::splat(2.0),
+        DQuat::from_rotation_z(FRAC_PI_2),
+        DVec3::new(0.0, -2.0, 0.0),
+    );
+    let mut simulation = Simulation::new(test_config(GizmoMode::TranslateX.into()), &[start]);
+    let update = |interaction| SimulationStep::UpdateWithParents(interaction, vec![parent.into()]);
+
+    simulation.step(update(interaction(X_ARROW, true, true)));
+    simulation.step(update(interaction((500.0, 400.0), false, true)));
+    assert_ne!(simulation.targets(), &[start]);
+
+    simulation.cancel();
+    assert_eq!(simulation.targets(), &[start]);
+}
+
+#[test]
+fn cancel_affine_restores_start_matrices() {
+    let sheared = DMat4::from_cols(DVec4::X, DVec4::new(1.0, 1.0, 0.0, 0.0), DVec4::Z, DVec4::W);
+    let mut simulation = Simulation::new(test_config(GizmoMode::TranslateX.into()), &[]);
+    simulation.set_matrices(vec![sheared.into()]);
+
+    simulation.step(SimulationStep::UpdateAffine(interaction(
+        X_ARROW, true, true,
+    )));
+    simulation.step(SimulationStep::UpdateAffine(interaction(
+        (500.0, 400.0),
+        false,
+        true,
+    )));
+    simulation.cancel();
+
+    assert_eq!(DMat4::from(simulation.matrices()[0]), sheared);
+}
+
+#[test]
+fn rotation_pivots() {
+    let targets = [translated(-1.0, 0.0, 0.0), translated(1.0, 0.0, 0.0)];
+    let rotate = |pivot_point| {
+        let config = GizmoConfig {
+            pivot_point,
+            pivot_position: DVec3::new(1.0, 0.0, 0.0).into(),
+            ..forced(GizmoMode::RotateZ)
+        };
+        let mut simulation = Simulation::new(config, &targets);
+        type_value(&mut simulation, "90");
+        release(&mut simulation);
+        simulation
+            .targets()
+            .iter()
+            .map(|target| DVec3::from(target.translation))
+            .collect::<Vec<_>>()
+    };
+
+    let median = rotate(TransformPivotPoint::MedianPoint);
+    assert_vec_eq(median[0], DVec3::new(0.0, -1.0, 0.0));
+    assert_vec_eq(median[1], DVec3::new(0.0, 1.0, 0.0));
+
+    let individual = rotate(TransformPivotPoint::IndividualOrigins);
+    assert_vec_eq(individual[0], DVec3::new(-1.0, 0.0, 0.0));
+    assert_vec_eq(individual[1], DVec3::new(1.0, 0.0, 0.0));
+
+    let point = rotate(TransformPivotPoint::Point);
+    assert_vec_eq(point[0], DVec3::new(1.0, -2.0, 0.0));
+    assert_vec_eq(point[1], DVec3::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn absolute_snapping() {
+    let config = GizmoConfig {
+        snapping: true,
+        snap_mode: SnapMode::Absolute,
+        snap_distance: 1.0,
+        ..test_config(GizmoMode::TranslateX.into())
+    };
+    let mut simulation = Simulation::new(config, &[translated(0.3, 0.0, 0.0)]);
+
+    simulation.drag((470.0, 400.0), (620.0, 400.0), 5);
+
+    assert_vec_eq(
+        simulation.targets()[0].translation,
+        DVec3::new(2.0, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn per_axis_snapping() {
+    let config = GizmoConfig {
+        snapping: true,
+        snap_distance: 1.0,
+        snap_distance_per_axis: Some([0.7, 1.0, 1.0].into()),
+        ..test_config(GizmoMode::TranslateX.into())
+    };
+    let mut simulation = Simulation::new(config, &[translated(0.3, 0.0, 0.0)]);
+
+    simulation.drag((470.0, 400.0), (620.0, 400.0), 5);
+
+    // Relative snapping moves the target by a multiple of 0.7
+    assert_vec_eq(
+        simulation.targets()[0].translation,
+        DVec3::new(2.4, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn translation_limits() {
+    let mut simulation = Simulation::new(
+        test_config(GizmoMode::TranslateX.into()),
+        &[Transform::default()],
+    );
+    simulation.set_target_limits(&[TransformLimits {
+        translation: Some(GizmoBounds {
+            min: DVec3::splat(-1.0).into(),
+            max: DVec3::splat(1.0).into(),
+        }),
+        ..Default::default()
+    }]);
+
+    simulation.drag(X_ARROW, (750.0, 400.0), 5);
+
+    assert_vec_eq(
+        simulation.targets()[0].translation,
+        DVec3::new(1.0, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn locked_plane_axis() {
+    let config = GizmoConfig {
+        locked_translation: GizmoDirection::X.into(),
+        ..test_config(GizmoMode::TranslateXY.into())
+    };
+    let mut simulation = Simulation::new(config, &[Transform::default()]);
+
+    simulation.drag(XY_PLANE, (540.0, 260.0), 5);
+
+    let translation = DVec3::from(simulation.targets()[0].translation);
+    assert!(translation.x.abs() < 1e-9);
+    assert!(translation.y > 1.0);
+}
+
+#[test]
+fn projection_conventions_agree() {
+    let drag = |projection_matrix: DMat4, clip_space| {
+        let config = GizmoConfig {
+            projection_matrix: projection_matrix.into(),
+            clip_space,
+            ..test_config(GizmoMode::TranslateXY.into())
+        };
+        let mut simulation = Simulation::new(config, &[Transform::default()]);
+        simulation.drag(XY_PLANE, (520.0, 320.0), 5);
+        DVec3::from(simulation.targets()[0].translation)
+    };
+
+    let opengl = drag(
+        DMat4::perspective_rh_gl(1.0, 1.0, 0.1, 100.0),
+        ClipSpace::default(),
+    );
+    assert!(opengl.x > 0.0 && opengl.y > 0.0);
+
+    let vulkan = drag(
+        DMat4::from_scale(DVec3::new(1.0, -1.0, 1.0)) * DMat4::perspective_rh(1.0, 1.0, 0.1, 100.0),
+        ClipSpace {
+            depth_range: DepthRange::ZeroToOne,
+            y_down: true,
+            ..Default::default()
+        },
+    );
+    assert_vec_eq(vulkan, opengl);
+
+    let reversed_z = drag(
+        DMat4::perspective_rh(1.0, 1.0, 100.0, 0.1),
+        ClipSpace {
+            depth_range: DepthRange::ZeroToOne,
+            reversed_z: true,
+            ..Default::default()
+        },
+    );
+    assert_vec_eq(reversed_z, opengl);
+}
+
+#[test]
+fn orthographic_translation() {
+    let config = GizmoConfig {
+        projection_matrix: DMat4::orthographic_rh_gl(-5.0, 5.0, -5.0, 5.0, 0.1, 100.0).into(),
+        ..test_config(GizmoMode::TranslateX.into())
+    };
+    let mut simulation = Simulation::new(config, &[Transform::default()]);
+
+    // The viewport spans 10 units in 800 pixels
+    simulation.drag(X_ARROW, (530.0, 400.0), 5);
+
+    assert_vec_eq(
+        simulation.targets()[0].translation,
+        DVec3::new(1.0, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn default_modes_exclude_bounds() {
+    assert!(GizmoMode::all().is_superset(GizmoMode::all_bounds()));
+
+    let simulation = Simulation::new(GizmoConfig::default(), &[]);
+    assert!(simulation
+        .gizmo()
+        .config()
+        .modes
+        .is_disjoint(GizmoMode::all_bounds()));
+}
+
+#[test]
+fn bounds_follow_the_last_target() {
+    // The box X axis is the Y axis of the first target
+    let first = Transform::from_scale_rotation_translation(
+        DVec3::ONE,
+        DQuat::from_rotation_z(FRAC_PI_2),
+        DVec3::new(-2.0, 0.0, 0.0),
+    );
+    let mut simulation = Simulation::new(
+        test_config(GizmoMode::BoundsFace.into()),
+        &[first, translated(2.0, 0.0, 0.0)],
+    );
+
+    let face = simulation
+        .world_to_screen(DVec3::new(2.5, 0.0, 0.0).into())
+        .unwrap();
+    let to = simulation
+        .world_to_screen(DVec3::new(3.5, 0.0, 0.0).into())
+        .unwrap();
+    let result = simulation.drag(face, to, 5);
+
+    assert!(matches!(result, Some(GizmoResult::BoundsScale { .. })));
+    assert_vec_eq(simulation.targets()[0].scale, DVec3::new(1.0, 2.0, 1.0));
+    assert_vec_eq(simulation.targets()[1].scale, DVec3::new(2.0, 1.0, 1.0));
+    assert_vec_eq(
+        simulation.targets()[1].translation,
+        DVec3::new(2.5, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn affine_bounds_scale_follows_box_axes() {
+    let rotated = DMat4::from_rotation_z(FRAC_PI_2);
+    let mut simulation = Simulation::new(test_config(GizmoMode::BoundsFace.into()), &[]);
+    simulation.set_matrices(vec![rotated.into()]);
+
+    // The +X face of the box is on the world Y axis
+    let face = simulation
+        .world_to_screen(DVec3::new(0.0, 0.5, 0.0).into())
+        .unwrap();
+    let to = simulation
+        .world_to_screen(DVec3::new(0.0, 1.5, 0.0).into())
+        .unwrap();
+    simulation.drag_with(face, to, 5, SimulationStep::UpdateAffine);
+
+    let matrix = DMat4::from(simulation.matrices()[0]);
+    assert_vec_eq(
+        matrix.transform_vector3(DVec3::X),
+        DVec3::new(0.0, 2.0, 0.0),
+    );
+    assert_vec_eq(
+        matrix.transform_vector3(DVec3::Y),
+        DVec3::new(-1.0, 0.0, 0.0),
+    );
+    assert_vec_eq(matrix.w_axis.truncate(), DVec3::new(0.0, 0.5, 0.0));
+}
+
+/// Drags the X arrow of a gizmo at the origin outwards along the arrow,
+/// with affine updates.
+fn drag_affine_x_arrow(simulation: &mut Simulation, axis: DVec3) {
+    let center = Pos2::from(simulation.world_to_screen(DVec3::ZERO.into()).unwrap());
+    let direction =
+        (Pos2::from(simulation.world_to_screen(axis.into()).unwrap()) - center).normalized();
+
+    simulation.drag_with(
+        (center + direction * 50.0).into(),
+        (center + direction * 150.0).into(),
+        5,
+        SimulationStep::UpdateAffine,
+    );
+}
+
+#[test]
+fn sheared_and_mirrored_affines_translate_along_local_axes() {
+    let config = GizmoConfig {
+        orientation: GizmoOrientation::Local,
+        ..test_config(GizmoMode::TranslateX.into())
+    };
+
+    // Shear along X keeps the X axis, and mirroring only flips the scale
+    let sheared = DMat3::from_cols(DVec3::X, DVec3::new(1.0, 1.0, 0.0), DVec3::Z);
+    let mirrored = DMat3::from_quat(DQuat::from_rotation_y(0.5))
+        * DMat3::from_diagonal(DVec3::new(-2.0, 3.0, 4.0));
+
+    for (matrix3, axis) in [
+        (sheared, DVec3::X),
+        (mirrored, DQuat::from_rotation_y(0.5) * DVec3::X),
+    ] {
+        let mut simulation = Simulation::new(config, &[]);
+        simulation.set_matrices(vec![DMat4::from_mat3(matrix3).into()]);
+
+        drag_affine_x_arrow(&mut simulation, axis);
+
+        let matrix = DMat4::from(simulation.matrices()[0]);
+        let translation = matrix.w_axis.truncate();
+        assert!(translation.dot(axis) > 0.5);
+        assert_vec_eq(translation.cross(axis), DVec3::ZERO);
+        assert!(DMat3::from_mat4(matrix).abs_diff_eq(matrix3, 1e-9));
+    }
+}
+
+#[test]
+fn affine_limits_keep_the_shear() {
+    let sheared = DMat3::from_cols(DVec3::X, DVec3::new(1.0, 1.0, 0.0), DVec3::Z);
+    let mut simulation = Simulation::new(test_config(GizmoMode::TranslateX.into()), &[]);
+    simulation.set_matrices(vec![DMat4::from_mat3(sheared).into()]);
+    simulation.set_target_limits(&[TransformLimits {
+        translation: Some(GizmoBounds {
+            min: DVec3::splat(-1.0).into(),
+            max: DVec3::splat(1.0).into(),
+        }),
+        ..Default::default()
+    }]);
+
+    simulation.drag_with(X_ARROW, (750.0, 400.0), 5, SimulationStep::UpdateAffine);
+
+    let matrix = DMat4::from(simulation.matrices()[0]);
+    assert_vec_eq(matrix.w_axis.truncate(), DVec3::new(1.0, 0.0, 0.0));
+    assert!(DMat3::from_mat4(matrix).abs_diff_eq(sheared, 1e-9));
+}
+
+#[test]
+fn view_translation_is_not_snapped_to_the_grid() {
+    let config = GizmoConfig {
+        snapping: true,
+        snap_mode: SnapMode::Absolute,
+        snap_distance: 1.0,
+        ..forced(GizmoMode::TranslateView)
+    };
+    let mut simulation = Simulation::new(config, &[translated(0.3, 0.0, 0.4)]);
+
+    simulation.drag((400.0, 400.0), (470.0, 400.0), 5);
+
+    let translation = DVec3::from(simulation.targets()[0].translation);
+    assert!((translation.z - 0.4).abs() < 1e-9);
+    assert!(translation.x > 0.3);
+}
+
+#[test]
+fn typed_plane_translation() {
+    let mut simulation = Simulation::new(forced(GizmoMode::TranslateXY), &[Transform::default()]);
+
+    type_value(&mut simulation, "2");
+    release(&mut simulation);
+
+    assert_vec_eq(
+        simulation.targets()[0].translation,
+        DVec3::new(2.0, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn enter_finishes_with_the_typed_value() {
+    let mut simulation = Simulation::new(
+        test_config(GizmoMode::TranslateX.into()),
+        &[Transform::default()],
+    );
+
+    simulation.update(interaction(X_ARROW, true, true));
+    simulation.type_text("3");
+    simulation.step(SimulationStep::TypeKey(GizmoKey::Enter));
+    let frame = simulation.update(interaction(X_ARROW, false, true));
+
+    assert!(frame.result.is_some());
+    assert_eq!(frame.drag_phase, Some(GizmoDragPhase::Committed));
+    assert_vec_eq(frame.targets[0].translation, DVec3::new(3.0, 0.0, 0.0));
+
+    // The pointer is still down, but the interaction is over
+    let frame = simulation.update(interaction((600.0, 400.0), false, true));
+    assert!(frame.result.is_none());
+    assert_vec_eq(frame.targets[0].translation, DVec3::new(3.0, 0.0, 0.0));
+}
+
+#[test]
+fn release_keeps_the_typed_value() {
+    let mut simulation = Simulation::new(
+        test_config(GizmoMode::TranslateX.into()),
+        &[Transform::default()],
+    );
+
+    simulation.update(interaction(X_ARROW, true, true));
+    simulation.type_text("2");
+    let frame = simulation.update(interaction(X_ARROW, false, false));
+
+    assert!(frame.result.is_some());
+    assert_eq!(frame.drag_phase, Some(GizmoDragPhase::Committed));
+    assert_vec_eq(
+        simulation.targets()[0].translation,
+        DVec3::new(2.0, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn recording_round_trip() {
+    let mut config = GizmoConfig {
+        snapping: true,
+        snap_distance: 0.7,
+        orientation: GizmoOrientation::Gimbal(EulerOrder::ZYX),
+        pivot_point: TransformPivotPoint::Point,
+        pivot_position: DVec3::new(0.5, 0.0, 0.0).into(),
+        locked_rotation: GizmoDirection::View.into(),
+        snap_angle_per_axis: Some([0.1, 0.2, 0.3].into()),
+        surface_align_axis: Some(GizmoDirection::Y),
+        origin_offset: DVec3::new(1.0, 2.0, 3.0).into(),
+        pixels_per_point: 2.0,
+        ..forced(GizmoMode::TranslateX)
+    };
+    config.modes = GizmoMode::all();
+    config.visuals.gizmo_size = 200.0;
+    config.visuals.active_color = Some(Color32::from_rgb(1, 2, 3));
+    config.visuals.axis_labels = true;
+
+    let mut simulation = Simulation::new(config, &[translated(0.1, 0.2, 0.3)]);
+    simulation.drag((400.0, 400.0), (470.0, 400.0), 3);
+
+    simulation.set_target_limits(&[TransformLimits {
+        min_scale: Some(DVec3::splat(0.5).into()),
+        rotation_order: EulerOrder::YZX,
+        ..Default::default()
+    }]);
+    simulation.update_config(GizmoConfig {
+        mode_override: Some(GizmoMode::RotateZ),
+        snap_mode: SnapMode::Absolute,
+        ..config
+    });
+    type_value(&mut simulation, "45");
+    simulation.step(SimulationStep::TypeKey(GizmoKey::Char(' ')));
+    simulation.cancel();
+    simulation.drag_with((400.0, 400.0), (420.0, 380.0), 3, |interaction| {
+        SimulationStep::UpdateWithParents(interaction, vec![DMat4::from_rotation_x(0.3).into()])
+    });
+    simulation.drag_with(
+        (400.0, 400.0),
+        (380.0, 420.0),
+        3,
+        SimulationStep::UpdateAffine,
+    );
+
+    let text = simulation.recording().to_string();
+    let recording: Recording = text.parse().unwrap();
+
+    assert_eq!(format!("{:?}", recording.config), format!("{config:?}"));
+    assert_eq!(recording.to_string(), text);
+    assert_eq!(recording.frames.len(), simulation.frames().len());
+    assert_eq!(recording.first_mismatch(1e-9), None);
+
+    // Results are compared as well
+    let mut tampered = recording;
+    let frame = tampered
+        .frames
+        .iter_mut()
+        .position(|frame| frame.result.is_some())
+        .unwrap();
+    tampered.frames[frame].result = None;
+    assert!(matches!(
+        tampered.first_mismatch(1e-9),
+        Some(ReplayMismatch::Result { frame: mismatch, .. }) if mismatch == frame
+    ));
+}
+
+#[test]
+fn recording_keeps_every_setting() {
+    // Every setting differs from its default, so a setting that is not
+    // recorded is parsed back as the default and the comparison fails.
+    // The struct literals have no defaults, so new settings must be added here.
+    let visuals = GizmoVisuals {
+        x_color: Color32::from_rgb(1, 2, 3),
+        y_color: Color32::from_rgb(4, 5, 6),
+        z_color: Color32::from_rgb(7, 8, 9),
+        s_color: Color32::from_rgb(10, 11, 12),
+        inactive_alpha: 0.25,
+        highlight_alpha: 0.75,
+        inactive_color: Some(Color32::from_rgb(13, 14, 15)),
+        highlight_color: Some(Color32::from_rgb(16, 17, 18)),
+        active_color: Some(Color32::from_rgb(19, 20, 21)),
+        disabled_color: Color32::from_rgb(22, 23, 24),
+        stroke_width: 2.5,
+        gizmo_size: 120.0,
+        axis_labels: true,
+        label_size: 18.0,
+        value_readout: false,
+    };
+    let config = GizmoConfig {
+        view_matrix: DMat4::look_at_rh(DVec3::new(1.0, 2.0, 3.0), DVec3::ZERO, DVec3::Y).into(),
+        projection_matrix: DMat4::perspective_rh(0.8, 1.5, 0.2, 50.0).into(),
+        clip_space: ClipSpace {
+            depth_range: DepthRange::ZeroToOne,
+            reversed_z: true,
+            y_down: true,
+        },
+        viewport: Rect::from_min_size(Pos2::new(10.0, 20.0), emath::vec2(640.0, 480.0)),
+        origin_offset: DVec3::new(1.0, 2.0, 3.0).into(),
+        modes: GizmoMode::all(),
+        mode_override: Some(GizmoMode::BoundsCorner),
+        orientation: GizmoOrientation::Custom,
+        parent_rotation: DQuat::from_rotation_x(0.1).into(),
+        custom_orientation: DQuat::from_rotation_y(0.2).into(),
+        pivot_point: TransformPivotPoint::Point,
+        pivot_position: DVec3::new(0.5, 0.6, 0.7).into(),
+        locked_translation: GizmoDirection::X.into(),
+        locked_rotation: GizmoDirection::View.into(),
+        locked_scale: GizmoDirection::Y | GizmoDirection::Z,
+        bounds: GizmoBounds {
+            min: DVec3::new(-1.0, -2.0, -3.0).into(),
+            max: DVec3::new(1.0, 2.0, 3.0).into(),
+        },
+        edit_pivot: true,
+        snapping: true,
+        snap_mode: SnapMode::Absolute,
+        snap_angle: 0.3,
+        snap_distance: 0.7,
+        snap_scale: 0.2,
+        snap_angle_per_axis: Some([0.1, 0.2, 0.3].into()),
+        snap_distance_per_axis: Some([0.4, 0.5, 0.6].into()),
+        snap_scale_per_axis: Some([0.7, 0.8, 0.9].into()),
+        surface_align_axis: Some(GizmoDirection::Y),
+        snap_target_radius: 12.0,
+        visuals,
+        pixels_per_point: 2.0,
+    };
+
+    let recording: Recording = Simulation::new(config, &[])
+        .recording()
+        .to_string()
+        .parse()
+        .unwrap();
+
+    assert_eq!(format!("{:?}", recording.config), format!("{config:?}"));
+}
+
+#[test]
+fn recording_errors() {
+    let error = "transform-gizmo-recording 2\nupdate 0 0 true\n"
+        .parse::<Recording>()
+        .unwrap_err();
+    assert_eq!(error.line, 2);
+
+    let error = "transform-gizmo-recording 2\ncancel\nsnapping true\n"
+        .parse::<Recording>()
+        .unwrap_err();
+    assert_eq!(error.line, 3);
+
+    assert!("transform-gizmo-recording 1\n"
+        .parse::<Recording>()
+        .is_err());
+}
@@ -0,0 +1,61 @@
+//! Snapping translations to points provided by the host application,
+//! such as vertices, edges or the origins of other objects.
+
+use std::fmt::{Debug, Formatter};
+
+/// Provides points that translations can snap to.
+///
+/// The provider is queried while a translation subgizmo is dragged with snapping enabled.
+/// The dragged pivot snaps to the returned point if it is within
+/// [`crate::GizmoConfig::snap_target_radius`] pixels of the pivot on screen.
+/// The snapped position stays on the axis or plane being dragged.
+///
+/// Closures of the form `Fn(SnapQuery) -> Option<mint::Vector3<f64>>` implement this trait.
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use transform_gizmo::math::DVec3;
+/// # use transform_gizmo::snapping::SnapQuery;
+/// let vertices = vec![DVec3::new(1.0, 0.0, 0.0), DVec3::new(0.0, 2.0, 0.0)];
+///
+/// let mut gizmo = transform_gizmo::Gizmo::default();
+/// gizmo.set_snap_target_provider(Some(Arc::new(move |query: SnapQuery| {
+///     let point = DVec3::from(query.point);
+///     vertices
+///         .iter()
+///         .min_by(|a, b| a.distance(point).total_cmp(&b.distance(point)))
+///         .map(|vertex| (*vertex).into())
+/// })));
+/// ```
+pub trait SnapTargetProvider: Send + Sync {
+    /// Returns the snap point nearest to the queried point, if any.
+    fn snap_target(&self, query: SnapQuery) -> Option<mint::Vector3<f64>>;
+}
+
+impl<F> SnapTargetProvider for F
+where
+    F: Fn(SnapQuery) -> Option<mint::Vector3<f64>> + Send + Sync,
+{
+    fn snap_target(&self, query: SnapQuery) -> Option<mint::Vector3<f64>> {
+        self(query)
+    }
+}
+
+impl Debug for dyn SnapTargetProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SnapTargetProvider")
+    }
+}
+
+/// Query for the nearest snap point.
+#[derive(Debug, Copy, Clone)]
+pub struct SnapQuery {
+    /// Candidate position of the dragged pivot, in world space
+    pub point: mint::Vector3<f64>,
+    /// Origin of the pointer ray, in world space
+    pub ray_origin: mint::Vector3<f64>,
+    /// Direction of the pointer ray, in world space
+    pub ray_direction: mint::Vector3<f64>,
+}
@@ -2,20 +2,28 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::hash::{BuildHasher, Hash, Hasher};
 use std::ops::Deref;
+use std::sync::Arc;
 
 use enum_dispatch::enum_dispatch;
 
+use crate::config::GizmoHandleState;
+use crate::placement::SurfaceRaycast;
+use crate::snapping::SnapTargetProvider;
 use crate::{config::PreparedGizmoConfig, gizmo::Ray, GizmoDrawData, GizmoResult};
 
 pub(crate) use arcball::ArcballSubGizmo;
+pub(crate) use bounds::BoundsSubGizmo;
 pub(crate) use rotation::RotationSubGizmo;
 pub(crate) use scale::ScaleSubGizmo;
+pub(crate) use surface::SurfaceSubGizmo;
 pub(crate) use translation::TranslationSubGizmo;
 
 pub(crate) mod arcball;
+pub(crate) mod bounds;
 pub(crate) mod common;
 pub(crate) mod rotation;
 pub(crate) mod scale;
+pub(crate) mod surface;
 pub(crate) mod translation;
 
 #[derive(Clone, Debug)]
@@ -26,6 +34,8 @@ pub(crate) enum SubGizmo {
     Translate(TranslationSubGizmo),
     Scale(ScaleSubGizmo),
     Arcball(ArcballSubGizmo),
+    Bounds(BoundsSubGizmo),
+    Surface(SurfaceSubGizmo),
 }
 
 #[enum_dispatch]
@@ -38,12 +48,19 @@ pub(crate) trait SubGizmoControl {
     fn set_focused(&mut self, focused: bool);
     /// Sets whether this subgizmo is currently active.
     fn set_active(&mut self, active: bool);
+    /// Sets the value typed by the user, overriding the pointer-derived value.
+    fn set_typed_value(&mut self, value: Option<f64>);
+    /// Sets the provider of points that translations snap to.
+    fn set_snap_target_provider(&mut self, provider: Option<Arc<dyn SnapTargetProvider>>);
+    /// Sets the raycast used for placing targets on surfaces.
+    fn set_surface_raycast(&mut self, raycast: Option<Arc<dyn SurfaceRaycast>>);
     /// Returns true if this subgizmo is currently focused.
     fn is_focused(&self) -> bool;
     /// Returns true if this subgizmo is currently active.
     fn is_active(&self) -> bool;
     /// Pick the subgizmo based on pointer ray. If it is close enough to
     /// the mouse pointer, distance from camera to the subgizmo is returned.
+    /// Locked subgizmos are never picked.
     fn pick(&mut self, ray: Ray) -> Option<f64>;
     /// Update the subgizmo based on pointer ray and interaction.
     fn update(&mut self, ray: Ray) -> Option<GizmoResult>;
@@ -64,6 +81,10 @@ pub(crate) trait SubGizmoKind: 'static {
     fn draw(subgizmo: &SubGizmoConfig<Self>) -> GizmoDrawData
     where
         Self: Sized;
+    /// Whether all axes of the subgizmo are locked in the current configuration.
+    fn is_locked(subgizmo: &SubGizmoConfig<Self>) -> bool
+    where
+        Self: Sized;
 }
 
 #[derive(Clone, Debug)]
@@ -78,9 +99,18 @@ pub(crate) struct SubGizmoConfig<T: SubGizmoKind> {
     pub(crate) focused: bool,
     /// Whether this subgizmo is active this frame
     pub(crate) active: bool,
+    /// Whether the subgizmo is locked and cannot be interacted with
+    pub(crate) locked: bool,
     /// Opacity of the subgizmo for this frame.
     /// A fully invisible subgizmo cannot be interacted with.
     pub(crate) opacity: f32,
+    /// Exact value typed by the user while this subgizmo is active.
+    /// Overrides the value derived from the pointer position.
+    pub(crate) typed_value: Option<f64>,
+    /// Provider of points that translations snap to.
+    pub(crate) snap_target_provider: Option<Arc<dyn SnapTargetProvider>>,
+    /// Raycast used for placing targets on surfaces.
+    pub(crate) surface_raycast: Option<Arc<dyn SurfaceRaycast>>,
     /// Implementation-specific state of the subgizmo.
     pub(crate) state: T::State,
 }
@@ -103,14 +133,33 @@ where
         params.hash(&mut hasher);
         let id = hasher.finish();
 
-        Self {
+        let mut subgizmo = Self {
             id,
             params,
             config,
             focused: false,
             active: false,
+            locked: false,
             opacity: 0.0,
+            typed_value: None,
+            snap_target_provider: None,
+            surface_raycast: None,
             state: Default::default(),
+        };
+        subgizmo.locked = T::is_locked(&subgizmo);
+        subgizmo
+    }
+
+    /// Interaction state of the subgizmo, which determines its color
+    pub(crate) fn handle_state(&self) -> GizmoHandleState {
+        if self.locked {
+            GizmoHandleState::Disabled
+        } else if self.active {
+            GizmoHandleState::Active
+        } else if self.focused {
+            GizmoHandleState::Hovered
+        } else {
+            GizmoHandleState::Inactive
         }
     }
 }
@@ -124,6 +173,7 @@ where
     }
     fn update_config(&mut self, config: PreparedGizmoConfig) {
         self.config = config;
+        self.locked = T::is_locked(self);
     }
 
     fn set_focused(&mut self, focused: bool) {
@@ -134,6 +184,18 @@ where
         self.active = active;
     }
 
+    fn set_typed_value(&mut self, value: Option<f64>) {
+        self.typed_value = value;
+    }
+
+    fn set_snap_target_provider(&mut self, provider: Option<Arc<dyn SnapTargetProvider>>) {
+        self.snap_target_provider = provider;
+    }
+
+    fn set_surface_raycast(&mut self, raycast: Option<Arc<dyn SurfaceRaycast>>) {
+        self.surface_raycast = raycast;
+    }
+
     fn is_focused(&self) -> bool {
         self.focused
     }
@@ -143,7 +205,9 @@ where
     }
 
     fn pick(&mut self, ray: Ray) -> Option<f64> {
-        T::pick(self, ray)
+        // Locked subgizmos are still picked to update their visibility
+        let t = T::pick(self, ray);
+        t.filter(|_| !self.locked)
     }
 
     fn update(&mut self, ray: Ray) -> Option<GizmoResult> {
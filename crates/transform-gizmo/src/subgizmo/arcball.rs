@@ -1,7 +1,10 @@
-use crate::math::{screen_to_world, DQuat, Pos2};
-use crate::subgizmo::common::{draw_circle, pick_circle};
+use crate::config::GizmoHandleState;
+use crate::math::{DQuat, Pos2};
+use crate::subgizmo::common::{draw_circle, gizmo_color, pick_circle};
 use crate::subgizmo::{SubGizmoConfig, SubGizmoKind};
-use crate::{config::PreparedGizmoConfig, gizmo::Ray, GizmoDrawData, GizmoResult};
+use crate::{
+    config::PreparedGizmoConfig, gizmo::Ray, GizmoDirection, GizmoDrawData, GizmoMode, GizmoResult,
+};
 use ecolor::Color32;
 
 pub(crate) type ArcballSubGizmo = SubGizmoConfig<Arcball>;
@@ -40,9 +43,10 @@ impl SubGizmoKind for Arcball {
         let dir = ray.screen_pos - subgizmo.state.last_pos;
 
         let rotation_delta = if dir.length_sq() > f32::EPSILON {
-            let mat = subgizmo.config.view_projection.inverse();
-            let a = screen_to_world(subgizmo.config.viewport, mat, ray.screen_pos, 0.0);
-            let b = screen_to_world(subgizmo.config.viewport, mat, subgizmo.state.last_pos, 0.0);
+            let a = subgizmo.config.screen_to_world(ray.screen_pos, 0.0);
+            let b = subgizmo
+                .config
+                .screen_to_world(subgizmo.state.last_pos, 0.0);
 
             let origin = subgizmo.config.view_forward();
             let a = (a - origin).normalize();
@@ -65,11 +69,20 @@ impl SubGizmoKind for Arcball {
     fn draw(subgizmo: &ArcballSubGizmo) -> GizmoDrawData {
         draw_circle(
             &subgizmo.config,
-            Color32::WHITE.gamma_multiply(if subgizmo.focused { 0.10 } else { 0.0 }),
+            match subgizmo.handle_state() {
+                state @ (GizmoHandleState::Hovered | GizmoHandleState::Active) => {
+                    gizmo_color(&subgizmo.config, state, GizmoDirection::View).gamma_multiply(0.10)
+                }
+                GizmoHandleState::Inactive | GizmoHandleState::Disabled => Color32::TRANSPARENT,
+            },
             arcball_radius(&subgizmo.config),
             true,
         )
     }
+
+    fn is_locked(subgizmo: &ArcballSubGizmo) -> bool {
+        subgizmo.config.is_mode_locked(GizmoMode::Arcball)
+    }
 }
 
 /// Radius to use for outer circle subgizmos
@@ -0,0 +1,224 @@
+use crate::config::{GizmoHandleState, GizmoModeKind};
+use crate::math::{ray_to_plane_origin, ray_to_ray, round_to_interval, DMat4, DVec3};
+use crate::shape::ShapeBuidler;
+use crate::subgizmo::common::{gizmo_color, unlocked_axes};
+use crate::subgizmo::translation::point_on_plane;
+use crate::subgizmo::{SubGizmoConfig, SubGizmoKind};
+use crate::{
+    config::PreparedGizmoConfig, gizmo::Ray, GizmoDirection, GizmoDrawData, GizmoMode, GizmoResult,
+};
+use ecolor::Color32;
+
+pub(crate) type BoundsSubGizmo = SubGizmoConfig<Bounds>;
+
+#[derive(Debug, Copy, Clone, Hash)]
+pub(crate) struct BoundsParams {
+    pub mode: GizmoMode,
+    /// Side of the bounding box for each axis, either -1, 0 or 1.
+    /// Face handles have one non-zero component, edges two and corners three.
+    pub side: [i8; 3],
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub(crate) struct BoundsState {
+    /// Transform from the bounds space to world space at the start of the interaction
+    start_transform: DMat4,
+    /// Position of the handle in bounds space
+    handle: DVec3,
+    /// Position of the opposite side in bounds space
+    anchor: DVec3,
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub(crate) struct Bounds;
+
+impl SubGizmoKind for Bounds {
+    type Params = BoundsParams;
+    type State = BoundsState;
+
+    fn pick(subgizmo: &mut BoundsSubGizmo, ray: Ray) -> Option<f64> {
+        let transform = bounds_transform(&subgizmo.config);
+        let handle = handle_position(&subgizmo.config, subgizmo.side, 1.0);
+        let handle_world = transform.transform_point3(handle);
+
+        let (t, dist) = ray_to_plane_origin(
+            -subgizmo.config.view_forward(),
+            handle_world,
+            ray.origin,
+            ray.direction,
+        );
+
+        subgizmo.state.start_transform = transform;
+        subgizmo.state.handle = handle;
+        subgizmo.state.anchor = handle_position(&subgizmo.config, subgizmo.side, -1.0);
+
+        if dist <= handle_size(&subgizmo.config) + subgizmo.config.focus_distance as f64 {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    fn update(subgizmo: &mut BoundsSubGizmo, ray: Ray) -> Option<GizmoResult> {
+        let transform = subgizmo.state.start_transform;
+        let handle_world = transform.transform_point3(subgizmo.state.handle);
+
+        let axes = free_axes(subgizmo.side)
+            .map(|axis| transform.transform_vector3(axis).normalize())
+            .collect::<Vec<_>>();
+
+        let new_point = match axes.as_slice() {
+            [axis] => {
+                let (_, t) = ray_to_ray(ray.origin, ray.direction, handle_world, *axis);
+                handle_world + *axis * t
+            }
+            [a, b] => point_on_plane(a.cross(*b).normalize(), handle_world, ray)?,
+            _ => point_on_plane(-subgizmo.config.view_forward(), handle_world, ray)?,
+        };
+
+        let new_handle = transform.inverse().transform_point3(new_point);
+
+        // Locked axes keep their scale
+        let unlocked = unlocked_axes(&subgizmo.config, GizmoModeKind::Bounds);
+
+        let mut total = DVec3::ONE;
+        for i in 0..3 {
+            let extent = subgizmo.state.handle[i] - subgizmo.state.anchor[i];
+            if subgizmo.side[i] == 0 || extent.abs() < 1e-10 || unlocked[i] == 0.0 {
+                continue;
+            }
+
+            let mut factor = if let Some(factor) = subgizmo.typed_value {
+                factor
+            } else {
+                (new_handle[i] - subgizmo.state.anchor[i]) / extent
+            };
+
+            if subgizmo.typed_value.is_none() && subgizmo.config.snapping {
+                factor = round_to_interval(factor, subgizmo.config.snap_scales()[i]);
+            }
+
+            total[i] = factor.max(1e-4);
+        }
+
+        Some(GizmoResult::BoundsScale {
+            total: total.into(),
+            anchor: transform.transform_point3(subgizmo.state.anchor).into(),
+        })
+    }
+
+    fn draw(subgizmo: &BoundsSubGizmo) -> GizmoDrawData {
+        let config = &subgizmo.config;
+
+        let shape_builder = ShapeBuidler::new(
+            config.view_projection,
+            config.viewport,
+            config.pixels_per_point,
+        );
+
+        let center =
+            bounds_transform(config).transform_point3(handle_position(config, subgizmo.side, 1.0));
+        let size = handle_size(config);
+        let right = config.view_right() * size;
+        let up = config.view_up() * size;
+
+        // Face handles are colored by their axis, edges and corners use the view color
+        let direction = match subgizmo.mode {
+            GizmoMode::BoundsFace => match subgizmo.side {
+                [x, _, _] if x != 0 => GizmoDirection::X,
+                [_, y, _] if y != 0 => GizmoDirection::Y,
+                _ => GizmoDirection::Z,
+            },
+            _ => GizmoDirection::View,
+        };
+
+        shape_builder
+            .polygon(
+                &[
+                    center - right - up,
+                    center + right - up,
+                    center + right + up,
+                    center - right + up,
+                ],
+                gizmo_color(config, subgizmo.handle_state(), direction),
+                (0.0, Color32::TRANSPARENT),
+            )
+            .into()
+    }
+
+    fn is_locked(subgizmo: &BoundsSubGizmo) -> bool {
+        let unlocked = unlocked_axes(&subgizmo.config, GizmoModeKind::Bounds);
+        (0..3).all(|i| subgizmo.side[i] == 0 || unlocked[i] == 0.0)
+    }
+}
+
+/// Draws the outline of the bounding box.
+pub(crate) fn draw_bounds(config: &PreparedGizmoConfig) -> GizmoDrawData {
+    let shape_builder = ShapeBuidler::new(
+        config.view_projection * bounds_transform(config),
+        config.viewport,
+        config.pixels_per_point,
+    );
+
+    let min = DVec3::from(config.bounds.min);
+    let max = DVec3::from(config.bounds.max);
+    let corner = |x: bool, y: bool, z: bool| {
+        DVec3::new(
+            if x { max.x } else { min.x },
+            if y { max.y } else { min.y },
+            if z { max.z } else { min.z },
+        )
+    };
+
+    let stroke = (
+        config.visuals.stroke_width / 2.0,
+        config
+            .visuals
+            .state_color(config.visuals.s_color, GizmoHandleState::Inactive),
+    );
+
+    let mut draw_data = GizmoDrawData::default();
+    for a in [false, true] {
+        for b in [false, true] {
+            for (from, to) in [
+                (corner(false, a, b), corner(true, a, b)),
+                (corner(a, false, b), corner(a, true, b)),
+                (corner(a, b, false), corner(a, b, true)),
+            ] {
+                draw_data += shape_builder.line_segment(from, to, stroke).into();
+            }
+        }
+    }
+
+    draw_data
+}
+
+/// Transform from the local space of the bounds to world space.
+/// The box follows the last target.
+fn bounds_transform(config: &PreparedGizmoConfig) -> DMat4 {
+    DMat4::from_scale_rotation_translation(
+        config.active_scale,
+        config.active_rotation,
+        config.active_translation,
+    )
+}
+
+/// Position of a handle in bounds space. Negative `sign` gives the opposite side.
+fn handle_position(config: &PreparedGizmoConfig, side: [i8; 3], sign: f64) -> DVec3 {
+    let min = DVec3::from(config.bounds.min);
+    let max = DVec3::from(config.bounds.max);
+    let side = DVec3::new(side[0] as f64, side[1] as f64, side[2] as f64);
+
+    (min + max) * 0.5 + (max - min) * 0.5 * side * sign
+}
+
+fn handle_size(config: &PreparedGizmoConfig) -> f64 {
+    (config.scale_factor * config.visuals.stroke_width * 1.5) as f64
+}
+
+fn free_axes(side: [i8; 3]) -> impl Iterator<Item = DVec3> {
+    DVec3::AXES
+        .into_iter()
+        .zip(side)
+        .filter_map(|(axis, side)| (side != 0).then_some(axis))
+}
@@ -1,6 +1,8 @@
-use crate::math::{ray_to_plane_origin, segment_to_segment};
+use crate::config::{GizmoHandleState, GizmoModeKind};
+use crate::gizmo::GizmoText;
+use crate::math::{ray_to_plane_origin, segment_to_segment, world_to_screen};
 use crate::GizmoMode;
-use ecolor::Color32;
+use ecolor::{Color32, Rgba};
 use enumset::EnumSet;
 use std::ops::{Add, RangeInclusive};
 
@@ -174,7 +176,7 @@ pub(crate) fn pick_circle(
 pub(crate) fn draw_arrow(
     config: &PreparedGizmoConfig,
     opacity: f32,
-    focused: bool,
+    state: GizmoHandleState,
     direction: GizmoDirection,
     mode: GizmoMode,
 ) -> GizmoDrawData {
@@ -182,9 +184,9 @@ pub(crate) fn draw_arrow(
         return GizmoDrawData::default();
     }
 
-    let color = gizmo_color(config, focused, direction).gamma_multiply(opacity);
+    let color = gizmo_color(config, state, direction).gamma_multiply(opacity);
 
-    let transform = if config.local_space() {
+    let transform = if config.rotated_axes() {
         DMat4::from_rotation_translation(config.rotation, config.translation)
     } else {
         DMat4::from_translation(config.translation)
@@ -230,22 +232,34 @@ pub(crate) fn draw_arrow(
         );
     }
 
+    if has_label(config, mode) {
+        let label_offset = (config.visuals.label_size * config.scale_factor) as f64;
+        draw_data += draw_label(
+            config,
+            transform,
+            arrow_params.end + arrow_params.direction * label_offset,
+            mode,
+            color,
+        );
+    }
+
     draw_data
 }
 
 pub(crate) fn draw_plane(
     config: &PreparedGizmoConfig,
     opacity: f32,
-    focused: bool,
+    state: GizmoHandleState,
     direction: GizmoDirection,
+    mode: GizmoMode,
 ) -> GizmoDrawData {
     if opacity <= 1e-4 {
         return GizmoDrawData::default();
     }
 
-    let color = gizmo_color(config, focused, direction).gamma_multiply(opacity);
+    let color = gizmo_color(config, state, direction).gamma_multiply(opacity);
 
-    let transform = if config.local_space() {
+    let transform = if config.rotated_axes() {
         DMat4::from_rotation_translation(config.rotation, config.translation)
     } else {
         DMat4::from_translation(config.translation)
@@ -277,9 +291,62 @@ pub(crate) fn draw_plane(
             )
             .into(),
     );
+
+    if has_label(config, mode) {
+        draw_data += draw_label(config, transform, origin, mode, color);
+    }
+
     draw_data
 }
 
+/// Whether the handle of given mode is labeled. Scale handles overlapping
+/// translation handles of the same axes are not labeled, so that each label is drawn once.
+fn has_label(config: &PreparedGizmoConfig, mode: GizmoMode) -> bool {
+    config.visuals.axis_labels
+        && !(mode.is_scale()
+            && GizmoMode::all_from_axes(mode.axes())
+                .iter()
+                .any(|other| other.is_translate() && config.modes.contains(other)))
+}
+
+/// Draws the names of the axes of `mode`, such as "X" or "XY", centered at `position`.
+fn draw_label(
+    config: &PreparedGizmoConfig,
+    transform: DMat4,
+    position: DVec3,
+    mode: GizmoMode,
+    color: Color32,
+) -> GizmoDrawData {
+    let Some(position) = world_to_screen(
+        config.viewport,
+        config.view_projection * transform,
+        position,
+    ) else {
+        return GizmoDrawData::default();
+    };
+
+    let text = mode
+        .axes()
+        .iter()
+        .filter_map(|axis| match axis {
+            GizmoDirection::X => Some('X'),
+            GizmoDirection::Y => Some('Y'),
+            GizmoDirection::Z => Some('Z'),
+            GizmoDirection::View => None,
+        })
+        .collect();
+
+    GizmoDrawData {
+        texts: vec![GizmoText {
+            text,
+            position: [position.x, position.y],
+            color: Rgba::from(color).to_array(),
+            size: config.visuals.label_size,
+        }],
+        ..Default::default()
+    }
+}
+
 pub(crate) fn draw_circle(
     config: &PreparedGizmoConfig,
     color: Color32,
@@ -359,7 +426,7 @@ pub(crate) fn plane_global_origin(
     direction: GizmoDirection,
 ) -> DVec3 {
     let mut origin = plane_local_origin(config, direction);
-    if config.local_space() {
+    if config.rotated_axes() {
         origin = config.rotation * origin;
     }
     origin + config.translation
@@ -384,39 +451,49 @@ pub(crate) fn gizmo_local_normal(config: &PreparedGizmoConfig, direction: GizmoD
     }
 }
 
+/// Index of the coordinate axis that the given direction is closest to
+pub(crate) fn axis_index(direction: DVec3) -> usize {
+    let direction = direction.abs();
+    if direction.x >= direction.y && direction.x >= direction.z {
+        0
+    } else if direction.y >= direction.z {
+        1
+    } else {
+        2
+    }
+}
+
 pub(crate) fn gizmo_normal(config: &PreparedGizmoConfig, direction: GizmoDirection) -> DVec3 {
     let mut normal = gizmo_local_normal(config, direction);
 
-    if config.local_space() && direction != GizmoDirection::View {
+    if config.rotated_axes() && direction != GizmoDirection::View {
         normal = config.rotation * normal;
     }
 
     normal
 }
 
+/// Mask of the gizmo axes that are not locked for given kind of transformation.
+/// Components of locked axes are zero, others one.
+pub(crate) fn unlocked_axes(config: &PreparedGizmoConfig, kind: GizmoModeKind) -> DVec3 {
+    let locked = config.locked_axes(kind);
+    DVec3::from_array(
+        [GizmoDirection::X, GizmoDirection::Y, GizmoDirection::Z].map(|direction| {
+            if locked.contains(direction) {
+                0.0
+            } else {
+                1.0
+            }
+        }),
+    )
+}
+
 pub(crate) fn gizmo_color(
     config: &PreparedGizmoConfig,
-    focused: bool,
+    state: GizmoHandleState,
     direction: GizmoDirection,
 ) -> Color32 {
-    let color = match direction {
-        GizmoDirection::X => config.visuals.x_color,
-        GizmoDirection::Y => config.visuals.y_color,
-        GizmoDirection::Z => config.visuals.z_color,
-        GizmoDirection::View => config.visuals.s_color,
-    };
-
-    let color = if focused {
-        config.visuals.highlight_color.unwrap_or(color)
-    } else {
-        color
-    };
-
-    let alpha = if focused {
-        config.visuals.highlight_alpha
-    } else {
-        config.visuals.inactive_alpha
-    };
-
-    color.linear_multiply(alpha)
+    config
+        .visuals
+        .state_color(config.visuals.axis_color(direction), state)
 }
@@ -2,14 +2,17 @@ use std::f64::consts::{FRAC_PI_2, PI, TAU};
 
 use ecolor::Color32;
 
+use crate::config::{GizmoModeKind, SnapMode};
 use crate::math::{
     ray_to_plane_origin, rotation_align, round_to_interval, world_to_screen, DMat3, DMat4, DQuat,
     DVec2, DVec3, Pos2,
 };
 use crate::shape::ShapeBuidler;
-use crate::subgizmo::common::{gizmo_color, gizmo_local_normal, gizmo_normal, outer_circle_radius};
+use crate::subgizmo::common::{
+    axis_index, gizmo_color, gizmo_local_normal, gizmo_normal, outer_circle_radius,
+};
 use crate::subgizmo::{SubGizmoConfig, SubGizmoKind};
-use crate::{gizmo::Ray, GizmoDirection, GizmoDrawData, GizmoResult};
+use crate::{gizmo::Ray, GizmoDirection, GizmoDrawData, GizmoOrientation, GizmoResult};
 
 pub(crate) type RotationSubGizmo = SubGizmoConfig<Rotation>;
 
@@ -22,6 +25,8 @@ pub(crate) struct RotationParams {
 pub(crate) struct RotationState {
     start_axis_angle: f64,
     start_rotation_angle: f64,
+    /// Angle of the target orientation around the rotation axis
+    start_orientation_angle: f64,
     last_rotation_angle: f64,
     current_delta: f64,
 }
@@ -37,7 +42,7 @@ impl SubGizmoKind for Rotation {
         let radius = arc_radius(subgizmo);
         let config = subgizmo.config;
         let origin = config.translation;
-        let normal = gizmo_normal(&subgizmo.config, subgizmo.direction);
+        let normal = ring_normal(subgizmo);
         let tangent = tangent(subgizmo);
 
         let (t, dist_from_gizmo_origin) =
@@ -53,16 +58,14 @@ impl SubGizmoKind for Rotation {
         let angle = if subgizmo.direction == GizmoDirection::View {
             f64::atan2(tangent.cross(normal).dot(offset), tangent.dot(offset))
         } else {
-            let mut forward = config.view_forward();
-            if config.left_handed {
-                forward *= -1.0;
-            }
+            let forward = config.view_backward();
             f64::atan2(offset.cross(forward).dot(normal), offset.dot(forward))
         };
 
         let rotation_angle = rotation_angle(subgizmo, ray.screen_pos).unwrap_or(0.0);
         subgizmo.state.start_axis_angle = angle;
         subgizmo.state.start_rotation_angle = rotation_angle;
+        subgizmo.state.start_orientation_angle = twist_angle(config.active_rotation, normal);
         subgizmo.state.last_rotation_angle = rotation_angle;
         subgizmo.state.current_delta = 0.0;
 
@@ -77,33 +80,63 @@ impl SubGizmoKind for Rotation {
     fn update(subgizmo: &mut RotationSubGizmo, ray: Ray) -> Option<GizmoResult> {
         let config = subgizmo.config;
 
-        let mut rotation_angle = rotation_angle(subgizmo, ray.screen_pos)?;
-        if config.snapping {
-            rotation_angle = round_to_interval(
-                rotation_angle - subgizmo.state.start_rotation_angle,
-                config.snap_angle as f64,
-            ) + subgizmo.state.start_rotation_angle;
-        }
+        let angle_delta = if let Some(degrees) = subgizmo.typed_value {
+            // The typed angle is the total rotation of the interaction,
+            // so it is not limited to the smallest angle.
+            let total_angle = -degrees.to_radians();
+            subgizmo.state.last_rotation_angle = subgizmo.state.start_rotation_angle + total_angle;
+            total_angle - subgizmo.state.current_delta
+        } else {
+            let mut rotation_angle = rotation_angle(subgizmo, ray.screen_pos)?;
+            if config.snapping {
+                let snap_angle = snap_angle(subgizmo);
+                let start_rotation_angle = subgizmo.state.start_rotation_angle;
+                rotation_angle = match config.snap_mode {
+                    SnapMode::Relative => {
+                        round_to_interval(rotation_angle - start_rotation_angle, snap_angle)
+                            + start_rotation_angle
+                    }
+                    SnapMode::Absolute => {
+                        // The orientation angle changes opposite to the rotation angle
+                        let start_orientation_angle = subgizmo.state.start_orientation_angle;
+                        let orientation_angle =
+                            start_orientation_angle - (rotation_angle - start_rotation_angle);
+                        let snapped_angle = round_to_interval(orientation_angle, snap_angle);
+                        start_rotation_angle + start_orientation_angle - snapped_angle
+                    }
+                };
+            }
 
-        let mut angle_delta = rotation_angle - subgizmo.state.last_rotation_angle;
+            let mut angle_delta = rotation_angle - subgizmo.state.last_rotation_angle;
 
-        // Always take the smallest angle, e.g. -10° instead of 350°
-        if angle_delta > PI {
-            angle_delta -= TAU;
-        } else if angle_delta < -PI {
-            angle_delta += TAU;
-        }
+            // Always take the smallest angle, e.g. -10° instead of 350°
+            if angle_delta > PI {
+                angle_delta -= TAU;
+            } else if angle_delta < -PI {
+                angle_delta += TAU;
+            }
+
+            subgizmo.state.last_rotation_angle = rotation_angle;
+            angle_delta
+        };
 
-        subgizmo.state.last_rotation_angle = rotation_angle;
         subgizmo.state.current_delta += angle_delta;
 
         let normal = gizmo_local_normal(&subgizmo.config, subgizmo.direction);
 
+        let euler_channel = match config.orientation() {
+            GizmoOrientation::Gimbal(_) if subgizmo.direction != GizmoDirection::View => {
+                Some(subgizmo.direction)
+            }
+            _ => None,
+        };
+
         Some(GizmoResult::Rotation {
             axis: normal.into(),
             delta: -angle_delta,
-            total: subgizmo.state.current_delta,
+            total: -subgizmo.state.current_delta,
             is_view_axis: subgizmo.direction == GizmoDirection::View,
+            euler_channel,
         })
     }
 
@@ -117,7 +150,11 @@ impl SubGizmoKind for Rotation {
             config.pixels_per_point,
         );
 
-        let color = gizmo_color(&subgizmo.config, subgizmo.focused, subgizmo.direction);
+        let color = gizmo_color(
+            &subgizmo.config,
+            subgizmo.handle_state(),
+            subgizmo.direction,
+        );
         let stroke = (config.visuals.stroke_width, color);
 
         let radius = arc_radius(subgizmo);
@@ -151,11 +188,7 @@ impl SubGizmoKind for Rotation {
             let mut start_angle_2 = end_angle;
             let mut end_angle_2 = start_angle + TAU;
 
-            if config
-                .view_forward()
-                .dot(gizmo_normal(&config, subgizmo.direction))
-                < 0.0
-            {
+            if config.view_forward().dot(ring_normal(subgizmo)) < 0.0 {
                 // Swap start and end angles based on the view direction relative to gizmo normal.
                 // Otherwise the filled sector gets drawn incorrectly.
                 std::mem::swap(&mut start_angle, &mut end_angle);
@@ -200,8 +233,9 @@ impl SubGizmoKind for Rotation {
             // Draw snapping ticks
             if config.snapping {
                 let stroke_width = stroke.0 / 2.0;
-                for i in 0..((TAU / config.snap_angle as f64) as usize + 1) {
-                    let angle = i as f64 * config.snap_angle as f64 + end_angle;
+                let snap_angle = snap_angle(subgizmo);
+                for i in 0..((TAU / snap_angle) as usize + 1) {
+                    let angle = i as f64 * snap_angle + end_angle;
                     let pos = DVec3::new(angle.cos(), 0.0, angle.sin());
                     draw_data += shape_builder
                         .line_segment(
@@ -216,13 +250,20 @@ impl SubGizmoKind for Rotation {
 
         draw_data
     }
+
+    fn is_locked(subgizmo: &RotationSubGizmo) -> bool {
+        subgizmo
+            .config
+            .locked_axes(GizmoModeKind::Rotate)
+            .contains(subgizmo.direction)
+    }
 }
 
 /// Calculates angle of the rotation axis arc.
 /// The arc is a semicircle, which turns into a full circle when viewed
 /// directly from the front.
 fn arc_angle(subgizmo: &SubGizmoConfig<Rotation>) -> f64 {
-    let dot = gizmo_normal(&subgizmo.config, subgizmo.direction)
+    let dot = ring_normal(subgizmo)
         .dot(subgizmo.config.view_forward())
         .abs();
     let min_dot = 0.990;
@@ -248,22 +289,24 @@ fn rotation_matrix(subgizmo: &SubGizmoConfig<Rotation>) -> DMat4 {
         return DMat4::from_rotation_translation(rotation, subgizmo.config.translation);
     }
 
-    // First rotate towards the gizmo normal
-    let local_normal = gizmo_local_normal(&subgizmo.config, subgizmo.direction);
-    let rotation = rotation_align(DVec3::Y, local_normal);
-    let mut rotation = DQuat::from_mat3(&rotation);
     let config = subgizmo.config;
 
-    if config.local_space() {
-        rotation = config.rotation * rotation;
-    }
+    // First rotate towards the gizmo normal
+    let mut rotation = if let GizmoOrientation::Gimbal(_) = config.orientation() {
+        DQuat::from_rotation_arc(DVec3::Y, ring_normal(subgizmo))
+    } else {
+        let local_normal = gizmo_local_normal(&subgizmo.config, subgizmo.direction);
+        let mut rotation = DQuat::from_mat3(&rotation_align(DVec3::Y, local_normal));
+
+        if config.rotated_axes() {
+            rotation = config.rotation * rotation;
+        }
+        rotation
+    };
 
     let tangent = tangent(subgizmo);
-    let normal = gizmo_normal(&subgizmo.config, subgizmo.direction);
-    let mut forward = config.view_forward();
-    if config.left_handed {
-        forward *= -1.0;
-    }
+    let normal = ring_normal(subgizmo);
+    let forward = config.view_backward();
     let angle = f64::atan2(tangent.cross(forward).dot(normal), tangent.dot(forward));
 
     // Rotate towards the camera, along the rotation axis.
@@ -286,12 +329,7 @@ fn rotation_angle(subgizmo: &SubGizmoConfig<Rotation>, cursor_pos: Pos2) -> Opti
     }
 
     let mut angle = f64::atan2(delta.y, delta.x);
-    if subgizmo
-        .config
-        .view_forward()
-        .dot(gizmo_normal(&subgizmo.config, subgizmo.direction))
-        < 0.0
-    {
+    if subgizmo.config.view_forward().dot(ring_normal(subgizmo)) < 0.0 {
         angle *= -1.0;
     }
 
@@ -299,19 +337,39 @@ fn rotation_angle(subgizmo: &SubGizmoConfig<Rotation>, cursor_pos: Pos2) -> Opti
 }
 
 fn tangent(subgizmo: &SubGizmoConfig<Rotation>) -> DVec3 {
+    if let GizmoOrientation::Gimbal(_) = subgizmo.config.orientation() {
+        if subgizmo.direction != GizmoDirection::View {
+            // Same tangent as the rotation towards the normal in `rotation_matrix` produces
+            return DQuat::from_rotation_arc(DVec3::Y, ring_normal(subgizmo)) * DVec3::Z;
+        }
+    }
+
     let mut tangent = match subgizmo.direction {
         GizmoDirection::X | GizmoDirection::Y => DVec3::Z,
         GizmoDirection::Z => -DVec3::Y,
         GizmoDirection::View => -subgizmo.config.view_right(),
     };
 
-    if subgizmo.config.local_space() && subgizmo.direction != GizmoDirection::View {
+    if subgizmo.config.rotated_axes() && subgizmo.direction != GizmoDirection::View {
         tangent = subgizmo.config.rotation * tangent;
     }
 
     tangent
 }
 
+/// Rotation axis of the subgizmo in world space.
+/// In gimbal orientation, the axes follow the Euler axes and are not necessarily orthogonal.
+fn ring_normal(subgizmo: &SubGizmoConfig<Rotation>) -> DVec3 {
+    let config = &subgizmo.config;
+    match (config.orientation(), subgizmo.direction) {
+        (GizmoOrientation::Gimbal(order), direction) if direction != GizmoDirection::View => {
+            let local_normal = gizmo_local_normal(config, direction);
+            (order.gimbal_axes(config.rotation) * local_normal).normalize()
+        }
+        _ => gizmo_normal(config, subgizmo.direction),
+    }
+}
+
 fn arc_radius(subgizmo: &SubGizmoConfig<Rotation>) -> f64 {
     if subgizmo.direction == GizmoDirection::View {
         outer_circle_radius(&subgizmo.config)
@@ -319,3 +377,20 @@ fn arc_radius(subgizmo: &SubGizmoConfig<Rotation>) -> f64 {
         (subgizmo.config.scale_factor * subgizmo.config.visuals.gizmo_size) as f64
     }
 }
+
+/// Angle increment for snapping rotations around the subgizmo axis
+fn snap_angle(subgizmo: &SubGizmoConfig<Rotation>) -> f64 {
+    match subgizmo.direction {
+        GizmoDirection::View => subgizmo.config.snap_angle as f64,
+        direction => {
+            let axis = gizmo_local_normal(&subgizmo.config, direction);
+            subgizmo.config.snap_angles()[axis_index(axis)]
+        }
+    }
+}
+
+/// Angle of the rotation around the given axis, ignoring rotation around other axes
+fn twist_angle(rotation: DQuat, axis: DVec3) -> f64 {
+    let projection = DVec3::new(rotation.x, rotation.y, rotation.z).dot(axis.normalize());
+    2.0 * projection.atan2(rotation.w)
+}
@@ -1,10 +1,11 @@
 use glam::DVec3;
 
+use crate::config::{GizmoModeKind, SnapMode};
 use crate::math::{round_to_interval, world_to_screen, Pos2};
 
 use crate::subgizmo::common::{
     draw_arrow, draw_circle, draw_plane, gizmo_color, gizmo_local_normal, outer_circle_radius,
-    pick_arrow, pick_circle, pick_plane, plane_bitangent, plane_tangent,
+    pick_arrow, pick_circle, pick_plane, plane_bitangent, plane_tangent, unlocked_axes,
 };
 use crate::subgizmo::{common::TransformKind, SubGizmoConfig, SubGizmoKind};
 use crate::{gizmo::Ray, GizmoDirection, GizmoDrawData, GizmoMode, GizmoResult};
@@ -21,6 +22,7 @@ pub(crate) struct ScaleParams {
 #[derive(Default, Debug, Copy, Clone)]
 pub(crate) struct ScaleState {
     start_delta: f64,
+    start_scale: DVec3,
 }
 
 #[derive(Default, Debug, Copy, Clone)]
@@ -49,6 +51,7 @@ impl SubGizmoKind for Scale {
         subgizmo.opacity = pick_result.visibility as _;
 
         subgizmo.state.start_delta = start_delta;
+        subgizmo.state.start_scale = subgizmo.config.scale;
 
         if pick_result.picked {
             Some(pick_result.t)
@@ -58,22 +61,40 @@ impl SubGizmoKind for Scale {
     }
 
     fn update(subgizmo: &mut ScaleSubGizmo, ray: Ray) -> Option<GizmoResult> {
-        let mut delta = distance_from_origin_2d(subgizmo, ray.screen_pos)?;
-        delta /= subgizmo.state.start_delta;
-
-        if subgizmo.config.snapping {
-            delta = round_to_interval(delta, subgizmo.config.snap_scale as f64);
-        }
-        delta = delta.max(1e-4) - 1.0;
-
+        // Plane and uniform scaling affects only the unlocked axes
+        let unlocked = unlocked_axes(&subgizmo.config, GizmoModeKind::Scale);
         let direction = match (subgizmo.transform_kind, subgizmo.direction) {
             (TransformKind::Axis, _) => gizmo_local_normal(&subgizmo.config, subgizmo.direction),
-            (TransformKind::Plane, GizmoDirection::View) => DVec3::ONE,
-            (TransformKind::Plane, _) => (plane_bitangent(subgizmo.direction)
+            (TransformKind::Plane, GizmoDirection::View) => unlocked,
+            // A typed factor is applied exactly to both axes of the plane
+            (TransformKind::Plane, _) if subgizmo.typed_value.is_some() => {
+                (plane_bitangent(subgizmo.direction) + plane_tangent(subgizmo.direction)) * unlocked
+            }
+            (TransformKind::Plane, _) => ((plane_bitangent(subgizmo.direction)
                 + plane_tangent(subgizmo.direction))
-            .normalize(),
+                * unlocked)
+                .normalize_or_zero(),
         };
 
+        let mut delta = if let Some(factor) = subgizmo.typed_value {
+            factor
+        } else {
+            let mut delta = distance_from_origin_2d(subgizmo, ray.screen_pos)?;
+            delta /= subgizmo.state.start_delta;
+
+            if subgizmo.config.snapping {
+                delta = match subgizmo.config.snap_mode {
+                    SnapMode::Relative => round_to_interval(
+                        delta,
+                        subgizmo.config.snap_scales()[first_scaled_axis(direction)],
+                    ),
+                    SnapMode::Absolute => snap_scale_absolute(subgizmo, direction, delta),
+                };
+            }
+            delta
+        };
+        delta = delta.max(1e-4) - 1.0;
+
         let scale = DVec3::ONE + (direction * delta);
 
         Some(GizmoResult::Scale {
@@ -86,24 +107,60 @@ impl SubGizmoKind for Scale {
             (TransformKind::Axis, _) => draw_arrow(
                 &subgizmo.config,
                 subgizmo.opacity,
-                subgizmo.focused,
+                subgizmo.handle_state(),
                 subgizmo.direction,
                 subgizmo.mode,
             ),
             (TransformKind::Plane, GizmoDirection::View) => draw_circle(
                 &subgizmo.config,
-                gizmo_color(&subgizmo.config, subgizmo.focused, subgizmo.direction),
+                gizmo_color(
+                    &subgizmo.config,
+                    subgizmo.handle_state(),
+                    subgizmo.direction,
+                ),
                 outer_circle_radius(&subgizmo.config),
                 false,
             ),
             (TransformKind::Plane, _) => draw_plane(
                 &subgizmo.config,
                 subgizmo.opacity,
-                subgizmo.focused,
+                subgizmo.handle_state(),
                 subgizmo.direction,
+                subgizmo.mode,
             ),
         }
     }
+
+    fn is_locked(subgizmo: &ScaleSubGizmo) -> bool {
+        subgizmo.config.is_mode_locked(subgizmo.mode)
+    }
+}
+
+/// Snaps the resulting scale to multiples of the snap increment.
+/// The first scaled axis is snapped, other axes are scaled with the same factor.
+fn snap_scale_absolute(subgizmo: &ScaleSubGizmo, direction: DVec3, factor: f64) -> f64 {
+    let i = first_scaled_axis(direction);
+
+    let weight = direction[i];
+    let start_scale = subgizmo.state.start_scale[i];
+    if weight.abs() < 1e-10 || start_scale.abs() < 1e-10 {
+        return factor;
+    }
+
+    let axis_factor = 1.0 + weight * (factor - 1.0);
+    let snapped_factor =
+        round_to_interval(start_scale * axis_factor, subgizmo.config.snap_scales()[i])
+            / start_scale;
+
+    1.0 + (snapped_factor - 1.0) / weight
+}
+
+/// Index of the first axis scaled in the given direction.
+/// Snapping of uniform and plane scaling follows this axis.
+fn first_scaled_axis(direction: DVec3) -> usize {
+    (0..3)
+        .find(|i| direction[*i].abs() > 1e-10)
+        .unwrap_or_default()
 }
 
 fn distance_from_origin_2d<T: SubGizmoKind>(
@@ -0,0 +1,86 @@
+use crate::math::DVec3;
+use crate::subgizmo::common::{draw_circle, gizmo_color, inner_circle_radius, pick_circle};
+use crate::subgizmo::{SubGizmoConfig, SubGizmoKind};
+use crate::{gizmo::Ray, GizmoDirection, GizmoDrawData, GizmoMode, GizmoResult};
+
+pub(crate) type SurfaceSubGizmo = SubGizmoConfig<Surface>;
+
+#[derive(Default, Debug, Copy, Clone)]
+pub(crate) struct SurfaceState {
+    start_translation: DVec3,
+    last_point: DVec3,
+    /// Normal of the latest surface hit, or [`None`] if nothing has been hit yet.
+    last_normal: Option<DVec3>,
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub(crate) struct Surface;
+
+impl SubGizmoKind for Surface {
+    type Params = ();
+    type State = SurfaceState;
+
+    fn pick(subgizmo: &mut SurfaceSubGizmo, ray: Ray) -> Option<f64> {
+        let pick_result = pick_circle(
+            &subgizmo.config,
+            ray,
+            inner_circle_radius(&subgizmo.config),
+            true,
+        );
+
+        subgizmo.state.start_translation = subgizmo.config.translation;
+        subgizmo.state.last_point = subgizmo.config.translation;
+        subgizmo.state.last_normal = None;
+
+        if pick_result.picked {
+            Some(pick_result.t)
+        } else {
+            None
+        }
+    }
+
+    fn update(subgizmo: &mut SurfaceSubGizmo, ray: Ray) -> Option<GizmoResult> {
+        // Without a surface under the pointer, the targets stay where they were last placed
+        let hit = subgizmo
+            .surface_raycast
+            .as_ref()
+            .and_then(|raycast| raycast.raycast(ray.origin.into(), ray.direction.into()));
+
+        let (new_point, normal) = match hit {
+            Some(hit) => (
+                DVec3::from(hit.point),
+                DVec3::from(hit.normal).normalize_or_zero(),
+            ),
+            None => (subgizmo.state.last_point, subgizmo.state.last_normal?),
+        };
+
+        let delta = new_point - subgizmo.state.last_point;
+        let total = new_point - subgizmo.state.start_translation;
+
+        subgizmo.state.last_point = new_point;
+        subgizmo.state.last_normal = Some(normal);
+
+        Some(GizmoResult::Surface {
+            delta: delta.into(),
+            total: total.into(),
+            normal: normal.into(),
+        })
+    }
+
+    fn draw(subgizmo: &SurfaceSubGizmo) -> GizmoDrawData {
+        draw_circle(
+            &subgizmo.config,
+            gizmo_color(
+                &subgizmo.config,
+                subgizmo.handle_state(),
+                GizmoDirection::View,
+            ),
+            inner_circle_radius(&subgizmo.config),
+            true,
+        )
+    }
+
+    fn is_locked(subgizmo: &SurfaceSubGizmo) -> bool {
+        subgizmo.config.is_mode_locked(GizmoMode::TranslateSurface)
+    }
+}
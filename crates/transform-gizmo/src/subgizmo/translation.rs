@@ -1,11 +1,14 @@
-use crate::math::{intersect_plane, ray_to_ray, round_to_interval, DVec3};
+use crate::config::{GizmoModeKind, SnapMode};
+use crate::math::{intersect_plane, ray_to_ray, round_to_interval, world_to_screen, DQuat, DVec3};
+use crate::snapping::SnapQuery;
 
 use crate::subgizmo::common::{
-    draw_arrow, draw_circle, draw_plane, gizmo_color, gizmo_normal, inner_circle_radius,
-    pick_arrow, pick_circle, pick_plane, plane_bitangent, plane_global_origin, plane_tangent,
+    axis_index, draw_arrow, draw_circle, draw_plane, gizmo_color, gizmo_local_normal, gizmo_normal,
+    inner_circle_radius, pick_arrow, pick_circle, pick_plane, plane_bitangent, plane_global_origin,
+    plane_tangent, unlocked_axes,
 };
 use crate::subgizmo::{common::TransformKind, SubGizmoConfig, SubGizmoKind};
-use crate::{gizmo::Ray, GizmoDirection, GizmoDrawData, GizmoMode, GizmoOrientation, GizmoResult};
+use crate::{gizmo::Ray, GizmoDirection, GizmoDrawData, GizmoMode, GizmoResult};
 
 pub(crate) type TranslationSubGizmo = SubGizmoConfig<Translation>;
 
@@ -20,6 +23,7 @@ pub(crate) struct TranslationParams {
 pub(crate) struct TranslationState {
     start_view_dir: DVec3,
     start_point: DVec3,
+    start_translation: DVec3,
     last_point: DVec3,
     current_delta: DVec3,
 }
@@ -49,6 +53,7 @@ impl SubGizmoKind for Translation {
 
         subgizmo.state.start_view_dir = subgizmo.config.view_forward();
         subgizmo.state.start_point = pick_result.subgizmo_point;
+        subgizmo.state.start_translation = subgizmo.config.translation;
         subgizmo.state.last_point = pick_result.subgizmo_point;
         subgizmo.state.current_delta = DVec3::ZERO;
 
@@ -80,19 +85,34 @@ impl SubGizmoKind for Translation {
 
         let mut new_delta = new_point - subgizmo.state.start_point;
 
-        if subgizmo.config.snapping {
-            new_delta = if subgizmo.transform_kind == TransformKind::Axis {
-                snap_translation_vector(subgizmo, new_delta)
-            } else {
-                snap_translation_plane(subgizmo, new_delta)
+        if let Some(distance) = subgizmo.typed_value {
+            new_delta = typed_translation(subgizmo, distance);
+            new_point = subgizmo.state.start_point + new_delta;
+        } else if let Some(target_delta) = snap_to_target(subgizmo, ray, new_delta) {
+            new_delta = target_delta;
+            new_point = subgizmo.state.start_point + new_delta;
+        } else if subgizmo.config.snapping {
+            new_delta = match (subgizmo.config.snap_mode, subgizmo.transform_kind) {
+                (SnapMode::Absolute, _) => snap_translation_absolute(subgizmo, new_delta),
+                (SnapMode::Relative, TransformKind::Axis) => {
+                    snap_translation_vector(subgizmo, new_delta)
+                }
+                (SnapMode::Relative, TransformKind::Plane) => {
+                    snap_translation_plane(subgizmo, new_delta)
+                }
             };
             new_point = subgizmo.state.start_point + new_delta;
         }
 
+        if subgizmo.transform_kind == TransformKind::Plane {
+            new_delta = remove_locked_axes(subgizmo, new_delta);
+            new_point = subgizmo.state.start_point + new_delta;
+        }
+
         let mut translation_delta = new_point - subgizmo.state.last_point;
         let mut total_translation = new_point - subgizmo.state.start_point;
 
-        if subgizmo.config.orientation() == GizmoOrientation::Local {
+        if subgizmo.config.rotated_axes() {
             let inverse_rotation = subgizmo.config.rotation.inverse();
             translation_delta = inverse_rotation * translation_delta;
             total_translation = inverse_rotation * total_translation;
@@ -112,24 +132,49 @@ impl SubGizmoKind for Translation {
             (TransformKind::Axis, _) => draw_arrow(
                 &subgizmo.config,
                 subgizmo.opacity,
-                subgizmo.focused,
+                subgizmo.handle_state(),
                 subgizmo.direction,
                 subgizmo.mode,
             ),
             (TransformKind::Plane, GizmoDirection::View) => draw_circle(
                 &subgizmo.config,
-                gizmo_color(&subgizmo.config, subgizmo.focused, subgizmo.direction),
+                gizmo_color(
+                    &subgizmo.config,
+                    subgizmo.handle_state(),
+                    subgizmo.direction,
+                ),
                 inner_circle_radius(&subgizmo.config),
                 false,
             ),
             (TransformKind::Plane, _) => draw_plane(
                 &subgizmo.config,
                 subgizmo.opacity,
-                subgizmo.focused,
+                subgizmo.handle_state(),
                 subgizmo.direction,
+                subgizmo.mode,
             ),
         }
     }
+
+    fn is_locked(subgizmo: &TranslationSubGizmo) -> bool {
+        subgizmo.config.is_mode_locked(subgizmo.mode)
+    }
+}
+
+/// Removes the movement along locked gizmo axes
+fn remove_locked_axes(subgizmo: &SubGizmoConfig<Translation>, delta: DVec3) -> DVec3 {
+    let unlocked = unlocked_axes(&subgizmo.config, GizmoModeKind::Translate);
+    if unlocked == DVec3::ONE {
+        return delta;
+    }
+
+    let rotation = if subgizmo.config.rotated_axes() {
+        subgizmo.config.rotation
+    } else {
+        DQuat::IDENTITY
+    };
+
+    rotation * (unlocked * (rotation.inverse() * delta))
 }
 
 /// Finds the nearest point on line that points in translation subgizmo direction
@@ -142,7 +187,30 @@ fn point_on_axis(subgizmo: &SubGizmoConfig<Translation>, ray: Ray) -> DVec3 {
     origin + direction * subgizmo_t
 }
 
-fn point_on_plane(plane_normal: DVec3, plane_origin: DVec3, ray: Ray) -> Option<DVec3> {
+/// Translation delta for a distance typed by the user.
+/// Axis translation moves exactly along the axis. Plane translation moves along
+/// the first unlocked axis of the plane, and view translation along the camera's right axis.
+fn typed_translation(subgizmo: &SubGizmoConfig<Translation>, distance: f64) -> DVec3 {
+    let direction = match (subgizmo.transform_kind, subgizmo.direction) {
+        (_, GizmoDirection::View) => subgizmo.config.view_right(),
+        (TransformKind::Axis, direction) => gizmo_normal(&subgizmo.config, direction),
+        (TransformKind::Plane, plane_direction) => {
+            // First unlocked axis of the plane, in X, Y, Z order
+            let unlocked = unlocked_axes(&subgizmo.config, GizmoModeKind::Translate);
+            [GizmoDirection::X, GizmoDirection::Y, GizmoDirection::Z]
+                .into_iter()
+                .zip(unlocked.to_array())
+                .find(|&(direction, unlocked)| direction != plane_direction && unlocked != 0.0)
+                .map_or(DVec3::ZERO, |(direction, _)| {
+                    gizmo_normal(&subgizmo.config, direction)
+                })
+        }
+    };
+
+    direction * distance
+}
+
+pub(crate) fn point_on_plane(plane_normal: DVec3, plane_origin: DVec3, ray: Ray) -> Option<DVec3> {
     let mut t = 0.0;
     if !intersect_plane(
         plane_normal,
@@ -157,11 +225,81 @@ fn point_on_plane(plane_normal: DVec3, plane_origin: DVec3, ray: Ray) -> Option<
     }
 }
 
+/// Snaps the dragged pivot to the nearest point given by the snap target provider.
+/// The pivot stays on the axis or plane of the subgizmo.
+fn snap_to_target(
+    subgizmo: &SubGizmoConfig<Translation>,
+    ray: Ray,
+    new_delta: DVec3,
+) -> Option<DVec3> {
+    let config = &subgizmo.config;
+    if !config.snapping {
+        return None;
+    }
+
+    let provider = subgizmo.snap_target_provider.as_ref()?;
+
+    let start_translation = subgizmo.state.start_translation;
+    let pivot = start_translation + new_delta;
+
+    let target = DVec3::from(provider.snap_target(SnapQuery {
+        point: pivot.into(),
+        ray_origin: ray.origin.into(),
+        ray_direction: ray.direction.into(),
+    })?);
+
+    let pivot_screen_pos = world_to_screen(config.viewport, config.view_projection, pivot)?;
+    let target_screen_pos = world_to_screen(config.viewport, config.view_projection, target)?;
+    if pivot_screen_pos.distance(target_screen_pos) > config.snap_target_radius {
+        return None;
+    }
+
+    let offset = target - start_translation;
+    let normal = gizmo_normal(config, subgizmo.direction);
+
+    Some(match (subgizmo.transform_kind, subgizmo.direction) {
+        (_, GizmoDirection::View) => offset,
+        (TransformKind::Axis, _) => normal * offset.dot(normal),
+        (TransformKind::Plane, _) => offset - normal * offset.dot(normal),
+    })
+}
+
+/// Snaps the resulting gizmo position to multiples of the snap distance.
+/// Only the coordinates along the moved axes are snapped.
+fn snap_translation_absolute(subgizmo: &SubGizmoConfig<Translation>, new_delta: DVec3) -> DVec3 {
+    let rotation = if subgizmo.config.rotated_axes() {
+        subgizmo.config.rotation
+    } else {
+        DQuat::IDENTITY
+    };
+
+    let axes = match (subgizmo.transform_kind, subgizmo.direction) {
+        // The view plane is not aligned to the grid, so snapping would move
+        // the target out of the plane. View translation is not snapped.
+        (_, GizmoDirection::View) => return new_delta,
+        (TransformKind::Axis, direction) => vec![gizmo_local_normal(&subgizmo.config, direction)],
+        (TransformKind::Plane, direction) => {
+            vec![plane_bitangent(direction), plane_tangent(direction)]
+        }
+    };
+
+    let start_translation = subgizmo.state.start_translation;
+    let mut position = rotation.inverse() * (start_translation + new_delta);
+    for axis in axes {
+        let i = axis_index(axis);
+        position[i] = round_to_interval(position[i], subgizmo.config.snap_distances()[i]);
+    }
+
+    rotation * position - start_translation
+}
+
 fn snap_translation_vector(subgizmo: &SubGizmoConfig<Translation>, new_delta: DVec3) -> DVec3 {
+    let axis = gizmo_local_normal(&subgizmo.config, subgizmo.direction);
+    let snap_distance = subgizmo.config.snap_distances()[axis_index(axis)];
+
     let delta_length = new_delta.length();
     if delta_length > 1e-5 {
-        new_delta / delta_length
-            * round_to_interval(delta_length, subgizmo.config.snap_distance as f64)
+        new_delta / delta_length * round_to_interval(delta_length, snap_distance)
     } else {
         new_delta
     }
@@ -170,7 +308,10 @@ fn snap_translation_vector(subgizmo: &SubGizmoConfig<Translation>, new_delta: DV
 fn snap_translation_plane(subgizmo: &SubGizmoConfig<Translation>, new_delta: DVec3) -> DVec3 {
     let mut bitangent = plane_bitangent(subgizmo.direction);
     let mut tangent = plane_tangent(subgizmo.direction);
-    if subgizmo.config.local_space() {
+    let snap_distances = subgizmo.config.snap_distances();
+    let bitangent_snap_distance = snap_distances[axis_index(bitangent)];
+    let tangent_snap_distance = snap_distances[axis_index(tangent)];
+    if subgizmo.config.rotated_axes() {
         bitangent = subgizmo.config.rotation * bitangent;
         tangent = subgizmo.config.rotation * tangent;
     }
@@ -181,10 +322,8 @@ fn snap_translation_plane(subgizmo: &SubGizmoConfig<Translation>, new_delta: DVe
     let n = gizmo_normal(&subgizmo.config, subgizmo.direction);
 
     if lb > 1e-5 && lt > 1e-5 {
-        bitangent * round_to_interval(lt, subgizmo.config.snap_distance as f64) * (ct / lt).dot(n)
-            + tangent
-                * round_to_interval(lb, subgizmo.config.snap_distance as f64)
-                * (cb / lb).dot(n)
+        bitangent * round_to_interval(lt, bitangent_snap_distance) * (ct / lt).dot(n)
+            + tangent * round_to_interval(lb, tangent_snap_distance) * (cb / lb).dot(n)
     } else {
         new_delta
     }
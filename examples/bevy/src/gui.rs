@@ -3,7 +3,10 @@ use bevy_egui::{
     egui::{self, Layout, RichText, Widget},
     EguiContexts, EguiPlugin,
 };
-use transform_gizmo_bevy::{config::TransformPivotPoint, prelude::*};
+use transform_gizmo_bevy::{
+    config::{EulerOrder, SnapMode, TransformPivotPoint},
+    prelude::*,
+};
 
 pub struct GuiPlugin;
 
@@ -41,6 +44,7 @@ fn draw_gizmo_result(ui: &mut egui::Ui, gizmo_result: Option<GizmoResult>) {
                 delta: _,
                 total,
                 is_view_axis: _,
+                euler_channel: _,
             } => {
                 format!(
                     "Rotation axis: ({:.2}, {:.2}, {:.2}), Angle: {:.2} deg",
@@ -50,13 +54,13 @@ fn draw_gizmo_result(ui: &mut egui::Ui, gizmo_result: Option<GizmoResult>) {
                     total.to_degrees()
                 )
             }
-            GizmoResult::Translation { delta: _, total } => {
+            GizmoResult::Translation { delta: _, total } | GizmoResult::Surface { total, .. } => {
                 format!(
                     "Translation: ({:.2}, {:.2}, {:.2})",
                     total.x, total.y, total.z,
                 )
             }
-            GizmoResult::Scale { total } => {
+            GizmoResult::Scale { total } | GizmoResult::BoundsScale { total, .. } => {
                 format!("Scale: ({:.2}, {:.2}, {:.2})", total.x, total.y, total.z,)
             }
             GizmoResult::Arcball { delta: _, total } => {
@@ -155,7 +159,12 @@ fn draw_options(ui: &mut egui::Ui, gizmo_options: &mut GizmoOptions) {
             egui::ComboBox::from_id_source("orientation_cb")
                 .selected_text(format!("{:?}", gizmo_options.gizmo_orientation))
                 .show_ui(ui, |ui| {
-                    for orientation in [GizmoOrientation::Global, GizmoOrientation::Local] {
+                    for orientation in [
+                        GizmoOrientation::Global,
+                        GizmoOrientation::Local,
+                        GizmoOrientation::View,
+                        GizmoOrientation::Gimbal(EulerOrder::XYZ),
+                    ] {
                         ui.selectable_value(
                             &mut gizmo_options.gizmo_orientation,
                             orientation,
@@ -172,6 +181,8 @@ fn draw_options(ui: &mut egui::Ui, gizmo_options: &mut GizmoOptions) {
                     for pivot_point in [
                         TransformPivotPoint::MedianPoint,
                         TransformPivotPoint::IndividualOrigins,
+                        TransformPivotPoint::BoundingBoxCenter,
+                        TransformPivotPoint::ActiveElement,
                     ] {
                         ui.selectable_value(
                             &mut gizmo_options.pivot_point,
@@ -182,6 +193,20 @@ fn draw_options(ui: &mut egui::Ui, gizmo_options: &mut GizmoOptions) {
                 });
             ui.end_row();
 
+            ui.label("Snap mode");
+            egui::ComboBox::from_id_source("snap_mode_cb")
+                .selected_text(format!("{:?}", gizmo_options.snap_mode))
+                .show_ui(ui, |ui| {
+                    for snap_mode in [SnapMode::Relative, SnapMode::Absolute] {
+                        ui.selectable_value(
+                            &mut gizmo_options.snap_mode,
+                            snap_mode,
+                            format!("{:?}", snap_mode),
+                        );
+                    }
+                });
+            ui.end_row();
+
             ui.label("Group targets");
             egui::Checkbox::without_text(&mut gizmo_options.group_targets).ui(ui);
             ui.end_row();
@@ -1,5 +1,7 @@
 use eframe::{egui, NativeOptions};
+use transform_gizmo_egui::config::{ClipSpace, DepthRange, EulerOrder, GizmoTheme};
 use transform_gizmo_egui::math::{DQuat, Transform};
+use transform_gizmo_egui::navigation::ViewCube;
 use transform_gizmo_egui::{
     math::{DMat4, DVec3},
     *,
@@ -7,9 +9,12 @@ use transform_gizmo_egui::{
 
 struct ExampleApp {
     gizmo: Gizmo,
+    view_cube: ViewCube,
+    view_rotation: DQuat,
 
     gizmo_modes: EnumSet<GizmoMode>,
     gizmo_orientation: GizmoOrientation,
+    theme: GizmoTheme,
 
     scale: DVec3,
     rotation: DQuat,
@@ -20,8 +25,15 @@ impl ExampleApp {
     fn new() -> Self {
         Self {
             gizmo: Gizmo::default(),
-            gizmo_modes: GizmoMode::all(),
+            view_cube: ViewCube::default(),
+            view_rotation: DQuat::from_mat4(&DMat4::look_at_lh(
+                DVec3::splat(5.0),
+                DVec3::ZERO,
+                DVec3::Y,
+            )),
+            gizmo_modes: GizmoMode::all() - GizmoMode::all_bounds(),
             gizmo_orientation: GizmoOrientation::Local,
+            theme: GizmoTheme::default(),
             scale: DVec3::ONE,
             rotation: DQuat::IDENTITY,
             translation: DVec3::ZERO,
@@ -37,9 +49,29 @@ impl ExampleApp {
             (viewport.width() / viewport.height()).into(),
             0.1,
         );
+        let clip_space = ClipSpace {
+            depth_range: DepthRange::ZeroToOne,
+            reversed_z: true,
+            y_down: false,
+        };
+
+        // The camera orbits the origin. Clicking the view cube rotates the camera.
+        let view_forward = self.view_rotation.inverse() * DVec3::Z;
+        let view_matrix = DMat4::from_quat(self.view_rotation)
+            * DMat4::from_translation(view_forward * DVec3::splat(5.0).length());
+
+        self.view_cube.update_config(GizmoConfig {
+            view_matrix: view_matrix.into(),
+            projection_matrix: projection_matrix.into(),
+            clip_space,
+            viewport,
+            visuals: self.theme.visuals(),
+            ..Default::default()
+        });
 
-        // Fixed camera position
-        let view_matrix = DMat4::look_at_lh(DVec3::splat(5.0), DVec3::ZERO, DVec3::Y);
+        if let Some(result) = self.view_cube.interact(ui) {
+            self.view_rotation = result.view_rotation.into();
+        }
 
         // Ctrl toggles snapping
         let snapping = ui.input(|input| input.modifiers.ctrl);
@@ -47,10 +79,12 @@ impl ExampleApp {
         self.gizmo.update_config(GizmoConfig {
             view_matrix: view_matrix.into(),
             projection_matrix: projection_matrix.into(),
+            clip_space,
             viewport,
             modes: self.gizmo_modes,
             orientation: self.gizmo_orientation,
             snapping,
+            visuals: self.theme.visuals(),
             ..Default::default()
         });
 
@@ -74,6 +108,7 @@ impl ExampleApp {
                     delta: _,
                     total,
                     is_view_axis: _,
+                    euler_channel: _,
                 } => {
                     format!(
                         "Rotation axis: ({:.2}, {:.2}, {:.2}), Angle: {:.2} deg",
@@ -83,13 +118,14 @@ impl ExampleApp {
                         total.to_degrees()
                     )
                 }
-                GizmoResult::Translation { delta: _, total } => {
+                GizmoResult::Translation { delta: _, total }
+                | GizmoResult::Surface { total, .. } => {
                     format!(
                         "Translation: ({:.2}, {:.2}, {:.2})",
                         total.x, total.y, total.z,
                     )
                 }
-                GizmoResult::Scale { total } => {
+                GizmoResult::Scale { total } | GizmoResult::BoundsScale { total, .. } => {
                     format!("Scale: ({:.2}, {:.2}, {:.2})", total.x, total.y, total.z,)
                 }
                 GizmoResult::Arcball { delta: _, total } => {
@@ -135,7 +171,12 @@ impl ExampleApp {
                 egui::ComboBox::from_id_salt("orientation_cb")
                     .selected_text(format!("{:?}", self.gizmo_orientation))
                     .show_ui(ui, |ui| {
-                        for orientation in [GizmoOrientation::Global, GizmoOrientation::Local] {
+                        for orientation in [
+                            GizmoOrientation::Global,
+                            GizmoOrientation::Local,
+                            GizmoOrientation::View,
+                            GizmoOrientation::Gimbal(EulerOrder::XYZ),
+                        ] {
                             ui.selectable_value(
                                 &mut self.gizmo_orientation,
                                 orientation,
@@ -144,6 +185,16 @@ impl ExampleApp {
                         }
                     });
                 ui.end_row();
+
+                ui.label("Theme");
+                egui::ComboBox::from_id_salt("theme_cb")
+                    .selected_text(format!("{:?}", self.theme))
+                    .show_ui(ui, |ui| {
+                        for theme in GizmoTheme::ALL {
+                            ui.selectable_value(&mut self.theme, theme, format!("{:?}", theme));
+                        }
+                    });
+                ui.end_row();
             });
     }
 }